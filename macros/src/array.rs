@@ -0,0 +1,114 @@
+use syn::spanned::Spanned as _;
+
+use crate::{
+    property::{expr_value, typed_expr_value},
+    Value,
+};
+
+#[derive(Clone)]
+pub(crate) struct Array {
+    pub elements: Vec<Value>,
+    pub element_type: Box<syn::Type>,
+}
+
+impl Array {
+    /// Creates an array from its default elements, inferring the element type from them unless one is given
+    /// explicitly with `[...] as [T]`.
+    pub fn new(array: syn::ExprArray, element_type: Option<syn::Type>) -> syn::Result<Self> {
+        let span = array.span();
+        let mut elements = array
+            .elems
+            .into_iter()
+            .map(|element| {
+                let span = element.span();
+                if let syn::Expr::Block(_) = element {
+                    return Err(syn::Error::new(
+                        span,
+                        "Tables are not supported as array elements, declare an array of tables with `[[heading]]` instead",
+                    ));
+                }
+                let value = match &element_type {
+                    Some(element_type) => typed_expr_value(element, element_type)?,
+                    None => expr_value(element)?,
+                };
+                Ok((value, span))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let element_type = Box::new(match element_type {
+            Some(element_type) => element_type,
+            None => infer_element_type(&mut elements, span)?,
+        });
+
+        Ok(Array {
+            elements: elements.into_iter().map(|(value, _span)| value).collect(),
+            element_type,
+        })
+    }
+}
+
+/// Infers the type shared by every element of an array. Unsuffixed integers are widened to floats when the array
+/// also contains floats, and unsuffixed integers take on the type of any suffixed ones.
+fn infer_element_type(
+    elements: &mut [(Value, proc_macro2::Span)],
+    span: proc_macro2::Span,
+) -> syn::Result<syn::Type> {
+    if elements.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "Cannot infer the element type of an empty array, write `[] as [T]` instead",
+        ));
+    }
+
+    let has_float = elements
+        .iter()
+        .any(|(value, _span)| matches!(value, Value::Float(_)));
+    if has_float {
+        for (value, span) in elements.iter_mut() {
+            if let Value::Integer(integer) = value {
+                if !integer.suffix().is_empty() {
+                    return Err(syn::Error::new(
+                        *span,
+                        "Array elements must all have the same type",
+                    ));
+                }
+                *value = Value::Float(syn::LitFloat::new(
+                    &format!("{}.0", integer.base10_digits()),
+                    integer.span(),
+                ));
+            }
+        }
+    }
+
+    let suffix = elements.iter().find_map(|(value, _span)| match value {
+        Value::Integer(integer) if !integer.suffix().is_empty() => Some(integer.suffix()),
+        Value::Float(float) if !float.suffix().is_empty() => Some(float.suffix()),
+        _ => None,
+    });
+
+    let mut element_type: Option<String> = None;
+    for (value, span) in elements.iter() {
+        let value_type = match (value, suffix) {
+            (Value::Integer(integer), Some(suffix)) if integer.suffix().is_empty() => {
+                crate::check_integer(integer.base10_digits(), suffix)
+                    .map_err(|error| syn::Error::new(*span, error))?;
+                suffix.to_owned()
+            }
+            (Value::Float(float), Some(suffix)) if float.suffix().is_empty() => suffix.to_owned(),
+            (value, _suffix) => value.rust_type().to_string(),
+        };
+
+        match &element_type {
+            Some(element_type) if *element_type != value_type => {
+                return Err(syn::Error::new(
+                    *span,
+                    "Array elements must all have the same type",
+                ))
+            }
+            Some(_) => {}
+            None => element_type = Some(value_type),
+        }
+    }
+
+    syn::parse_str(&element_type.unwrap_or_default())
+}
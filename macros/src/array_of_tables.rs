@@ -0,0 +1,451 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    constraints::Constraints,
+    docs::Docs,
+    document::insert_item,
+    inline_table::InlineTable,
+    item::{ChildItem, Item},
+    property::{ChildPropertyDefault, Property},
+    table::parse_heading,
+    table_attributes::TableAttributes,
+    toml_struct::TomlStruct,
+    type_name::TypeName,
+    TableContents, Value,
+};
+
+/// An array of tables, declared as `[[heading]]`. Every block with the heading is an element of the default value,
+/// and the first one also declares the schema of every element. A first block written as `[[heading]] = [];` only
+/// declares the schema, for an array that is empty by default.
+#[derive(Clone)]
+pub(crate) struct ArrayOfTables {
+    heading: syn::Ident,
+    items: Vec<Item>,
+    entries: Vec<TableContents>,
+
+    /// Whether the block is written as `[[heading]] = [];`, so that it isn't an element of the default value.
+    schema_only: bool,
+
+    /// The super-tables named before the last segment of a dotted heading such as `[[a.b.c]]`. These are
+    /// resolved, and this emptied, when the array is inserted into the document.
+    pub supertables: Vec<syn::Ident>,
+
+    pub attributes: TableAttributes,
+}
+
+impl syn::parse::Parse for ArrayOfTables {
+    fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+        let outer;
+        syn::bracketed!(outer in input);
+        let heading;
+        syn::bracketed!(heading in outer);
+        let (supertables, heading) = parse_heading(&heading)?;
+        if !outer.is_empty() {
+            return Err(outer.error("Expected `]]`"));
+        }
+        let schema_only = input.peek(syn::Token![=]);
+        if schema_only {
+            input.parse::<syn::Token![=]>()?;
+            let empty;
+            syn::bracketed!(empty in input);
+            if !empty.is_empty() {
+                return Err(empty.error("Expected `[]`"));
+            }
+            input.parse::<syn::Token![;]>()?;
+        }
+        let contents: TableContents = input.parse()?;
+        let mut items = Vec::new();
+        for property in contents.0 {
+            insert_item(&mut items, Item::from(property))?;
+        }
+        if let Some(required) = items
+            .iter()
+            .find(|item| !schema_only && !item.has_default())
+        {
+            return Err(syn::Error::new(
+                required.name().span(),
+                format!(
+                    "`{}` has no default, so the first block must be written as `[[{heading}]] = [];`",
+                    required.name()
+                ),
+            ));
+        }
+
+        Ok(ArrayOfTables {
+            heading,
+            items,
+            entries: Vec::new(),
+            schema_only,
+            supertables,
+            attributes: TableAttributes::default(),
+        })
+    }
+}
+
+impl ArrayOfTables {
+    pub fn heading(&self) -> &syn::Ident {
+        &self.heading
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Whether the first block is an element of the default value too, which it is unless it is written as
+    /// `[[heading]] = [];`.
+    pub fn first_is_entry(&self) -> bool {
+        !self.schema_only
+    }
+
+    /// The elements of the default value given by the blocks after the first, with the keys that each of them
+    /// sets.
+    pub fn entries(&self) -> &[TableContents] {
+        &self.entries
+    }
+
+    /// The items declared for every element of this array. Dotted headings that pass through this array add
+    /// to these.
+    pub fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
+    }
+
+    /// Adds the contents of a repeated `[[heading]]` block as a default element, checking it against the schema
+    /// declared by the first block.
+    pub fn add_entry(&mut self, entry: ArrayOfTables) -> syn::Result<()> {
+        if entry.schema_only {
+            return Err(syn::Error::new(
+                entry.heading.span(),
+                format!(
+                    "Only the first `[[{}]]` can be written as `[[{}]] = [];`",
+                    self.heading, self.heading
+                ),
+            ));
+        }
+        if !entry.attributes.is_empty() {
+            return Err(syn::Error::new(
+                entry.heading.span(),
+                format!(
+                    "Attributes must be given on the first `[[{}]]`",
+                    self.heading
+                ),
+            ));
+        }
+
+        let properties = check_entry(&self.items, entry.items, &self.heading)?;
+        if let Some(required) = self.items.iter().find(|item| {
+            !item.has_default()
+                && !properties
+                    .iter()
+                    .any(|property| &property.name == item.name())
+        }) {
+            return Err(syn::Error::new(
+                entry.heading.span(),
+                format!("Missing a value for the required key `{}`", required.name()),
+            ));
+        }
+
+        self.entries.push(TableContents(properties));
+        Ok(())
+    }
+
+    pub fn into_child_array_of_tables(self, parent_name: TypeName) -> ChildArrayOfTables {
+        ChildArrayOfTables {
+            array: self,
+            parent_name,
+        }
+    }
+}
+
+/// Checks the keys set by an element of the default value against the items declared for every element. Tables
+/// created by dotted keys, like `a` in `a.b = 5`, are kept as properties holding the keys they set.
+fn check_entry(
+    items: &[Item],
+    entry: Vec<Item>,
+    heading: &syn::Ident,
+) -> syn::Result<Vec<Property>> {
+    let mut properties = Vec::new();
+    for item in entry {
+        let expected = items.iter().find(|expected| expected.name() == item.name());
+        match (item, expected) {
+            (Item::Property(property), Some(Item::Property(expected))) => {
+                let Some(value) = property.value else {
+                    return Err(syn::Error::new(
+                        property.name.span(),
+                        "Elements of the default value need a value for each key they set",
+                    ));
+                };
+                let value = expected.check_override(value, property.name.span())?;
+                properties.push(Property {
+                    value: Some(value),
+                    explicit_type: expected.explicit_type.clone(),
+                    ..property
+                });
+            }
+            (Item::Table(table), Some(Item::Table(expected))) => {
+                let nested = check_entry(expected.items(), table.items().to_vec(), heading)?;
+                properties.push(Property {
+                    name: table.heading().clone(),
+                    value: Some(Value::InlineTable(InlineTable {
+                        properties: nested,
+                        dotted: true,
+                        attributes: TableAttributes::default(),
+                    })),
+                    explicit_type: None,
+                    constraints: Constraints::default(),
+                    docs: Docs::default(),
+                });
+            }
+            (item, None) => {
+                return Err(syn::Error::new(
+                    item.name().span(),
+                    format!(
+                        "`{}` is not declared by the first `[[{heading}]]`",
+                        item.name()
+                    ),
+                ))
+            }
+            (item, Some(_)) => {
+                return Err(syn::Error::new(
+                    item.name().span(),
+                    format!(
+                        "`{}` must be set the same way as in the first `[[{heading}]]`",
+                        item.name()
+                    ),
+                ))
+            }
+        }
+    }
+    Ok(properties)
+}
+
+#[derive(Clone)]
+pub struct ChildArrayOfTables {
+    array: ArrayOfTables,
+    parent_name: TypeName,
+}
+
+impl ChildArrayOfTables {
+    pub fn name(&self) -> &syn::Ident {
+        &self.array.heading
+    }
+
+    /// The doc comments given before the first `[[heading]]` of this array.
+    pub fn docs(&self) -> &Docs {
+        &self.array.attributes.docs
+    }
+
+    /// The name of the struct generated for the elements of this array.
+    pub fn type_name(&self) -> TypeName {
+        self.parent_name.child(&self.array.heading)
+    }
+
+    /// The items of the elements of this array, as items of the struct generated for them.
+    pub fn items(&self) -> Vec<ChildItem> {
+        let element_type = self.type_name();
+        self.array
+            .items
+            .iter()
+            .map(|item| item.clone().into_child_item(element_type.clone()))
+            .collect()
+    }
+
+    pub fn default_value(&self) -> proc_macro2::TokenStream {
+        let element_type = self.type_name();
+
+        // The first block sets no keys of its own, so it is an element with the defaults of every key
+        let first = self
+            .array
+            .first_is_entry()
+            .then(|| entry_value(&element_type, self.items(), &[]));
+        let entries = first
+            .into_iter()
+            .chain(
+                self.array
+                    .entries
+                    .iter()
+                    .map(|entry| entry_value(&element_type, self.items(), &entry.0)),
+            )
+            .collect::<Vec<_>>();
+
+        quote::quote! {
+            ::std::vec![#(#entries),*]
+        }
+    }
+}
+
+/// The value of an element of the default value, or of a table within it, with the keys it sets and the defaults
+/// of the rest.
+fn entry_value(
+    type_name: &TypeName,
+    items: Vec<ChildItem>,
+    entry: &[Property],
+) -> proc_macro2::TokenStream {
+    let fields = items
+        .into_iter()
+        .map(|item| {
+            let overridden = entry
+                .iter()
+                .find(|overridden| &overridden.name == item.name());
+            match (overridden, item) {
+                (
+                    Some(Property {
+                        value: Some(Value::InlineTable(table)),
+                        ..
+                    }),
+                    ChildItem::Table(child),
+                ) => {
+                    let name = child.name();
+                    let value = entry_value(&child.type_name(), child.items(), &table.properties);
+                    quote::quote! { #name: #value }
+                }
+                (Some(property), _) => {
+                    let property = ChildPropertyDefault(
+                        property.clone().into_child_property(type_name.clone()),
+                    );
+                    quote::quote! { #property }
+                }
+                (None, item) => {
+                    let default = item.into_item_default();
+                    quote::quote! { #default }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote::quote! {
+        #type_name {
+            #(
+                #fields
+            ),*
+        }
+    }
+}
+
+impl quote::ToTokens for ChildArrayOfTables {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let element_type = self.type_name();
+        let heading = &self.array.heading;
+        tokens.extend(quote::quote! {
+            #heading: ::std::vec::Vec<#element_type>
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining the struct for the
+/// elements of this array.
+#[derive(Clone)]
+pub struct ChildArrayOfTablesPrelude(pub ChildArrayOfTables);
+
+impl std::ops::Deref for ChildArrayOfTablesPrelude {
+    type Target = ChildArrayOfTables;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildArrayOfTablesPrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let items = self.items();
+
+        let name = self.type_name();
+        let prelude = items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_prelude())
+            .collect::<Vec<_>>();
+        let prelude = name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        let toml_struct = TomlStruct {
+            name,
+            items,
+            attributes: self.array.attributes.clone(),
+        };
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #toml_struct
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildArrayOfTablesDefault(pub ChildArrayOfTables);
+
+impl std::ops::Deref for ChildArrayOfTablesDefault {
+    type Target = ChildArrayOfTables;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildArrayOfTablesDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.array.heading.clone();
+        let default = self.default_value();
+
+        tokens.extend(quote::quote! {
+            #heading: #default
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the field initializer that
+/// reads this array out of a parsed TOML table, falling back to its default.
+#[derive(Clone)]
+pub struct ChildArrayOfTablesFromToml(pub ChildArrayOfTables);
+
+impl std::ops::Deref for ChildArrayOfTablesFromToml {
+    type Target = ChildArrayOfTables;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildArrayOfTablesFromToml {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.array.heading.clone();
+        let key = heading.unraw().to_string();
+        let default = self.default_value();
+
+        tokens.extend(quote::quote! {
+            #heading: match ::toml_schema::__private::get(table, #key, path)? {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => #default,
+            }
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the getter method of this
+/// array.
+#[derive(Clone)]
+pub struct ChildArrayOfTablesGetter(pub ChildArrayOfTables);
+
+impl std::ops::Deref for ChildArrayOfTablesGetter {
+    type Target = ChildArrayOfTables;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildArrayOfTablesGetter {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.array.heading.clone();
+        let element_type = self.type_name();
+
+        tokens.extend(quote::quote! {
+            pub fn #heading(&self) -> &[#element_type] {
+                &self.#heading
+            }
+        });
+    }
+}
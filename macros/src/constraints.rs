@@ -0,0 +1,529 @@
+use syn::spanned::Spanned as _;
+
+use crate::Value;
+
+/// The constraints on the value of a property, given as attributes like `#[range(1..=65535)]` or
+/// `#[pattern = "^[a-z]+$"]`. They are checked against the defaults in the schema when the macro expands, and
+/// against parsed and `try_set` values at runtime.
+#[derive(Clone, Default)]
+pub(crate) struct Constraints {
+    pub range: Option<Box<Range>>,
+    pub pattern: Option<Pattern>,
+    pub min_len: Option<Length>,
+    pub max_len: Option<Length>,
+}
+
+impl Constraints {
+    /// Parses the constraints from the attributes of a property.
+    pub fn from_attributes(attributes: Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut constraints = Constraints::default();
+        for attribute in attributes {
+            let path = attribute.path();
+            let name = quote::quote! { #path }.to_string().replace(' ', "");
+            let duplicate = match name.as_str() {
+                "range" => constraints
+                    .range
+                    .replace(Box::new(attribute.parse_args()?))
+                    .is_some(),
+                "pattern" => constraints
+                    .pattern
+                    .replace(Pattern::new(name_value(&attribute)?)?)
+                    .is_some(),
+                "min_len" => constraints
+                    .min_len
+                    .replace(Length::new(name_value(&attribute)?)?)
+                    .is_some(),
+                "max_len" => constraints
+                    .max_len
+                    .replace(Length::new(name_value(&attribute)?)?)
+                    .is_some(),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        format!("Unknown attribute `{name}`"),
+                    ))
+                }
+            };
+            if duplicate {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!("`{name}` is given more than once"),
+                ));
+            }
+        }
+
+        if let (Some(min_len), Some(max_len)) = (&constraints.min_len, &constraints.max_len) {
+            if min_len.value > max_len.value {
+                return Err(syn::Error::new(
+                    max_len.span,
+                    "`max_len` is less than `min_len`",
+                ));
+            }
+        }
+        Ok(constraints)
+    }
+
+    /// Checks that constraints are only given for values they apply to.
+    pub fn check_applicable(&self, value_type: &ValueType) -> syn::Result<()> {
+        if let Some(range) = &self.range {
+            if !value_type.number {
+                return Err(syn::Error::new(
+                    range.span,
+                    "`range` can only be used on numbers",
+                ));
+            }
+            range.check_type(value_type)?;
+        }
+
+        let string_constraints = [
+            (
+                "pattern",
+                self.pattern.as_ref().map(|pattern| pattern.source.span()),
+            ),
+            ("min_len", self.min_len.as_ref().map(|length| length.span)),
+            ("max_len", self.max_len.as_ref().map(|length| length.span)),
+        ];
+        for (name, span) in string_constraints {
+            if let (Some(span), false) = (span, value_type.string) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{name}` can only be used on strings"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a default value in the schema against these constraints.
+    pub fn check(&self, value: &Value, span: proc_macro2::Span) -> syn::Result<()> {
+        if let Some(range) = &self.range {
+            range.check(value, span)?;
+        }
+
+        let Value::String(value) = value else {
+            return Ok(());
+        };
+        let value = value.value();
+        if let Some(pattern) = &self.pattern {
+            if !pattern.regex.is_match(&value) {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "Expected a value matching the pattern `{}`",
+                        pattern.source.value()
+                    ),
+                ));
+            }
+        }
+        let length = value.chars().count();
+        if let Some(min_len) = &self.min_len {
+            if length < min_len.value {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "Expected a value at least {} characters long",
+                        min_len.value
+                    ),
+                ));
+            }
+        }
+        if let Some(max_len) = &self.max_len {
+            if length > max_len.value {
+                return Err(syn::Error::new(
+                    span,
+                    format!("Expected a value at most {} characters long", max_len.value),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The statements that check the value in the variable `value` at runtime, returning an error for the key
+    /// at `path` if it doesn't satisfy these constraints. Optional values are only checked when they are set.
+    pub fn runtime_checks(
+        &self,
+        value_type: &ValueType,
+        path: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let mut checks = Vec::new();
+        if let Some(range) = &self.range {
+            let bounds = range.bounds(value_type.float);
+            let description = range.to_string();
+            checks.push(quote::quote! {
+                ::toml_schema::__private::check_range(value, #bounds, #description, #path)?;
+            });
+        }
+        if let Some(pattern) = &self.pattern {
+            let source = &pattern.source;
+            checks.push(quote::quote! {
+                static PATTERN: ::std::sync::OnceLock<::toml_schema::__private::Regex> =
+                    ::std::sync::OnceLock::new();
+                ::toml_schema::__private::check_pattern(value, &PATTERN, #source, #path)?;
+            });
+        }
+        if self.min_len.is_some() || self.max_len.is_some() {
+            let min_len = match &self.min_len {
+                Some(Length { value, .. }) => {
+                    quote::quote! { ::core::option::Option::Some(#value) }
+                }
+                None => quote::quote! { ::core::option::Option::None },
+            };
+            let max_len = match &self.max_len {
+                Some(Length { value, .. }) => {
+                    quote::quote! { ::core::option::Option::Some(#value) }
+                }
+                None => quote::quote! { ::core::option::Option::None },
+            };
+            checks.push(quote::quote! {
+                ::toml_schema::__private::check_length(value, #min_len, #max_len, #path)?;
+            });
+        }
+
+        if checks.is_empty() {
+            return quote::quote! {};
+        }
+        if value_type.optional {
+            quote::quote! {
+                if let ::core::option::Option::Some(value) = &value {
+                    #(#checks)*
+                }
+            }
+        } else {
+            quote::quote! {
+                {
+                    let value = &value;
+                    #(#checks)*
+                }
+            }
+        }
+    }
+
+    /// The statements that check an integer given for a key with a range against the range before it is
+    /// converted to the type of the key, so that integers that don't fit the type are reported as outside of the
+    /// range too. `integer` is an expression for the integer as an `Option<i128>`, which is `None` for values that
+    /// aren't integers.
+    pub fn integer_checks(
+        &self,
+        value_type: &ValueType,
+        integer: proc_macro2::TokenStream,
+        path: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match &self.range {
+            Some(range) if value_type.integer_type.is_some() => {
+                let bounds = range.bounds(false);
+                let description = range.to_string();
+                quote::quote! {
+                    if let ::core::option::Option::Some(value) = #integer {
+                        ::toml_schema::__private::check_range(&value, #bounds, #description, #path)?;
+                    }
+                }
+            }
+            _ => quote::quote! {},
+        }
+    }
+
+    /// The keywords that express these constraints in a JSON Schema.
+    pub fn json_schema(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut schema = serde_json::Map::new();
+        if let Some(range) = &self.range {
+            if let Some(start) = &range.start {
+                schema.insert("minimum".to_owned(), start.number.json());
+            }
+            if let Some(end) = &range.end {
+                let keyword = if range.inclusive {
+                    "maximum"
+                } else {
+                    "exclusiveMaximum"
+                };
+                schema.insert(keyword.to_owned(), end.number.json());
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            schema.insert("pattern".to_owned(), pattern.source.value().into());
+        }
+        if let Some(min_len) = &self.min_len {
+            schema.insert("minLength".to_owned(), min_len.value.into());
+        }
+        if let Some(max_len) = &self.max_len {
+            schema.insert("maxLength".to_owned(), max_len.value.into());
+        }
+        schema
+    }
+}
+
+/// The value of an attribute written as `#[name = value]`.
+fn name_value(attribute: &syn::Attribute) -> syn::Result<&syn::Expr> {
+    Ok(&attribute.meta.require_name_value()?.value)
+}
+
+/// A regular expression that string values must match, like `#[pattern = "^[a-z0-9-]+$"]`.
+#[derive(Clone)]
+pub(crate) struct Pattern {
+    source: syn::LitStr,
+    regex: regex::Regex,
+}
+
+impl Pattern {
+    fn new(expr: &syn::Expr) -> syn::Result<Self> {
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(source),
+            ..
+        }) = expr
+        else {
+            return Err(syn::Error::new_spanned(expr, "Expected a string"));
+        };
+        let regex = regex::Regex::new(&source.value())
+            .map_err(|error| syn::Error::new(source.span(), error))?;
+        Ok(Pattern {
+            source: source.clone(),
+            regex,
+        })
+    }
+}
+
+/// A bound on the number of characters in string values, like `#[max_len = 64]`.
+#[derive(Clone)]
+pub(crate) struct Length {
+    value: usize,
+    span: proc_macro2::Span,
+}
+
+impl Length {
+    fn new(expr: &syn::Expr) -> syn::Result<Self> {
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(value),
+            ..
+        }) = expr
+        else {
+            return Err(syn::Error::new_spanned(expr, "Expected a length"));
+        };
+        Ok(Length {
+            value: value.base10_parse()?,
+            span: value.span(),
+        })
+    }
+}
+
+/// What the constraints on a property need to know about its type.
+pub(crate) struct ValueType {
+    pub number: bool,
+    pub string: bool,
+    pub float: bool,
+    pub optional: bool,
+
+    /// The name of the integer type of the value, like `u16`, unless it isn't an integer.
+    pub integer_type: Option<String>,
+}
+
+/// A range of numbers, like `1..=65535`, `0.0..1.0` or `1..`.
+#[derive(Clone)]
+pub(crate) struct Range {
+    start: Option<Bound>,
+    end: Option<Bound>,
+    inclusive: bool,
+    span: proc_macro2::Span,
+}
+
+impl syn::parse::Parse for Range {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let syn::ExprRange {
+            start, limits, end, ..
+        } = input.parse()?;
+        let start = start.map(|start| Bound::new(*start)).transpose()?;
+        let end = end.map(|end| Bound::new(*end)).transpose()?;
+        let inclusive = matches!(limits, syn::RangeLimits::Closed(_));
+        if start.is_none() && end.is_none() {
+            return Err(syn::Error::new(
+                span,
+                "Expected a range with at least one bound",
+            ));
+        }
+        Ok(Range {
+            start,
+            end,
+            inclusive,
+            span,
+        })
+    }
+}
+
+impl Range {
+    fn check(&self, value: &Value, span: proc_macro2::Span) -> syn::Result<()> {
+        let value = match value {
+            Value::Integer(value) => Number::Integer(value.base10_digits().parse().unwrap()),
+            Value::Float(value) => Number::Float(value.base10_digits().parse().unwrap()),
+            _ => return Ok(()),
+        };
+
+        let above_start = self
+            .start
+            .as_ref()
+            .is_none_or(|start| value.ge(&start.number));
+        let below_end = self.end.as_ref().is_none_or(|end| match self.inclusive {
+            true => value.le(&end.number),
+            false => !value.ge(&end.number),
+        });
+        if !above_start || !below_end {
+            return Err(syn::Error::new(
+                span,
+                format!("Expected a value in the range `{self}`"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the bounds of this range can be compared with values of the given type, so that integer keys
+    /// don't get fractional bounds and every bound is a value of their type.
+    fn check_type(&self, value_type: &ValueType) -> syn::Result<()> {
+        for bound in self.start.iter().chain(&self.end) {
+            match (&bound.number, &value_type.integer_type) {
+                (Number::Float(_), Some(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        &bound.source,
+                        "Expected an integer bound for an integer key",
+                    ))
+                }
+                (Number::Integer(value), Some(integer_type)) => {
+                    crate::check_integer(&value.to_string(), integer_type)
+                        .map_err(|error| syn::Error::new_spanned(&bound.source, error))?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// The tokens for this range as a Rust range expression over integers or floats.
+    fn bounds(&self, float: bool) -> proc_macro2::TokenStream {
+        let start = self.start.as_ref().map(|start| start.number.literal(float));
+        let end = self.end.as_ref().map(|end| end.number.literal(float));
+        match self.inclusive {
+            true => quote::quote! { #start..=#end },
+            false => quote::quote! { #start..#end },
+        }
+    }
+}
+
+impl std::fmt::Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(start) = &self.start {
+            write!(f, "{}", start.number)?;
+        }
+        f.write_str(if self.inclusive { "..=" } else { ".." })?;
+        if let Some(end) = &self.end {
+            write!(f, "{}", end.number)?;
+        }
+        Ok(())
+    }
+}
+
+/// A bound of a range, along with the expression it is given as.
+#[derive(Clone)]
+struct Bound {
+    number: Number,
+    source: syn::Expr,
+}
+
+impl Bound {
+    fn new(source: syn::Expr) -> syn::Result<Self> {
+        let span = source.span();
+        let (negative, expr) = match source.clone() {
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => (true, *expr),
+            expr => (false, expr),
+        };
+        let sign = if negative { "-" } else { "" };
+        let number = match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(value),
+                ..
+            }) if value.suffix().is_empty() => format!("{sign}{}", value.base10_digits())
+                .parse()
+                .map(Number::Integer)
+                .ok(),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Float(value),
+                ..
+            }) if value.suffix().is_empty() => format!("{sign}{}", value.base10_digits())
+                .parse()
+                .map(Number::Float)
+                .ok(),
+            _ => None,
+        };
+        let number =
+            number.ok_or_else(|| syn::Error::new(span, "Expected a number without a suffix"))?;
+        Ok(Bound { number, source })
+    }
+}
+
+/// A bound of a range, or a default compared with one, which may be negative.
+#[derive(Clone)]
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn ge(&self, other: &Number) -> bool {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => a >= b,
+            (a, b) => a.as_f64() >= b.as_f64(),
+        }
+    }
+
+    fn le(&self, other: &Number) -> bool {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => a <= b,
+            (a, b) => a.as_f64() <= b.as_f64(),
+        }
+    }
+
+    fn json(&self) -> serde_json::Value {
+        match self {
+            Number::Integer(value) => match i64::try_from(*value) {
+                Ok(value) => value.into(),
+                Err(_) => (*value as f64).into(),
+            },
+            Number::Float(value) => (*value).into(),
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+
+    /// The tokens for this number as an unsuffixed literal, so that it takes the type of the value it is compared
+    /// with.
+    fn literal(&self, float: bool) -> proc_macro2::TokenStream {
+        match self {
+            Number::Integer(value) if float => {
+                let literal = proc_macro2::Literal::f64_unsuffixed(*value as f64);
+                quote::quote! { #literal }
+            }
+            Number::Integer(value) => {
+                let literal = proc_macro2::Literal::i128_unsuffixed(*value);
+                quote::quote! { #literal }
+            }
+            Number::Float(value) => {
+                let literal = proc_macro2::Literal::f64_unsuffixed(*value);
+                quote::quote! { #literal }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Integer(value) => write!(f, "{value}"),
+            Number::Float(value) => write!(f, "{value:?}"),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    constraints::{Constraints, ValueType},
+    docs::Docs,
+    getter_type, option_inner_type,
+    sample::{placeholder, type_string},
+    struct_impls::{StructField, StructImpls},
+    table_attributes::TableAttributes,
+    type_name, vec_element_type, INTEGER_TYPES,
+};
+
+/// A hand-written struct deriving `TomlSchema`, which gets the same runtime support as the structs generated by
+/// `toml!`. Keys that are left out take their value from the `Default` implementation of the struct.
+pub(crate) struct DeriveInput {
+    name: syn::Ident,
+    fields: Vec<Field>,
+    attributes: TableAttributes,
+}
+
+struct Field {
+    name: syn::Ident,
+    ty: syn::Type,
+    kind: FieldKind,
+    constraints: Constraints,
+    docs: Docs,
+}
+
+/// How a field is stored in TOML, which is decided by its type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    /// A key-value pair, for the types built into TOML, options, vectors and maps of them, and fields marked
+    /// with `#[toml(value)]`.
+    Value,
+
+    /// A nested struct, which also derives `TomlSchema`.
+    Table,
+
+    /// A `Vec` of nested structs.
+    ArrayOfTables,
+
+    /// A `BTreeMap<String, T>` of nested structs.
+    MapTable,
+}
+
+impl syn::parse::Parse for DeriveInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut input: syn::DeriveInput = input.parse()?;
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "`TomlSchema` cannot be derived for generic structs",
+            ));
+        }
+        let syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) = input.data
+        else {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`TomlSchema` can only be derived for structs with named fields",
+            ));
+        };
+
+        let docs = Docs::take(&mut input.attrs)?;
+        let mut attributes = TableAttributes {
+            docs,
+            ..TableAttributes::default()
+        };
+        for meta in toml_attributes(&input.attrs)? {
+            let syn::Meta::NameValue(syn::MetaNameValue { path, value, .. }) = meta else {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected an attribute like `#[toml(validate = path::to::function)]`",
+                ));
+            };
+            attributes.set(path, value)?;
+        }
+
+        let fields = fields
+            .named
+            .into_iter()
+            .map(Field::new)
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(DeriveInput {
+            name: input.ident,
+            fields,
+            attributes,
+        })
+    }
+}
+
+/// The arguments of every `#[toml(...)]` attribute among the given ones.
+fn toml_attributes(attributes: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut metas = Vec::new();
+    for attribute in attributes {
+        if attribute.path().is_ident("toml") {
+            metas.extend(attribute.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+            )?);
+        }
+    }
+    Ok(metas)
+}
+
+impl Field {
+    fn new(mut field: syn::Field) -> syn::Result<Self> {
+        let name = field.ident.expect("fields are named");
+        let docs = Docs::take(&mut field.attrs)?;
+
+        // `#[toml(range(1..=65535), pattern = "...")]` takes the same constraints as `toml!`
+        let mut value = false;
+        let mut constraint_attributes = Vec::new();
+        for meta in toml_attributes(&field.attrs)? {
+            if meta.path().is_ident("value") {
+                meta.require_path_only()?;
+                value = true;
+            } else {
+                constraint_attributes.push(syn::parse_quote! { #[#meta] });
+            }
+        }
+
+        let kind = match (value, field_kind(&field.ty)) {
+            (true, _) => FieldKind::Value,
+            (false, kind) => kind,
+        };
+        let constraints = Constraints::from_attributes(constraint_attributes)?;
+        let field = Field {
+            name,
+            ty: field.ty,
+            kind,
+            constraints,
+            docs,
+        };
+        field.constraints.check_applicable(&field.value_type())?;
+        Ok(field)
+    }
+
+    fn key(&self) -> String {
+        self.name.unraw().to_string()
+    }
+
+    fn value_type(&self) -> ValueType {
+        let inner_type = option_inner_type(&self.ty);
+        let name = type_name(inner_type.unwrap_or(&self.ty)).unwrap_or_default();
+        let float = matches!(name.as_str(), "f32" | "f64");
+        let integer = INTEGER_TYPES.contains(&name.as_str());
+        ValueType {
+            number: float || integer,
+            float,
+            string: name == "String",
+            optional: inner_type.is_some(),
+            integer_type: integer.then_some(name),
+        }
+    }
+
+    /// The type of this field as shown in the sample config, like `u16` or `String, optional`.
+    fn type_description(&self) -> String {
+        match option_inner_type(&self.ty) {
+            Some(inner_type) => format!("{}, optional", type_string(inner_type)),
+            None => type_string(&self.ty),
+        }
+    }
+}
+
+fn field_kind(ty: &syn::Type) -> FieldKind {
+    if is_value_type(ty) {
+        FieldKind::Value
+    } else if vec_element_type(ty).is_some() {
+        FieldKind::ArrayOfTables
+    } else if map_value_type(ty).is_some() {
+        FieldKind::MapTable
+    } else {
+        FieldKind::Table
+    }
+}
+
+/// Whether a type is stored as a key-value pair without being marked with `#[toml(value)]`.
+fn is_value_type(ty: &syn::Type) -> bool {
+    if let Some(inner_type) = option_inner_type(ty).or_else(|| vec_element_type(ty)) {
+        return is_value_type(inner_type);
+    }
+    if let Some(value_type) = map_value_type(ty) {
+        return is_value_type(value_type);
+    }
+    type_name(ty).is_some_and(|name| {
+        INTEGER_TYPES.contains(&name.as_str())
+            || matches!(name.as_str(), "f32" | "f64" | "bool" | "String" | "PathBuf")
+    })
+}
+
+/// The value type of a `BTreeMap<String, T>`.
+fn map_value_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "BTreeMap" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.iter().collect::<Vec<_>>().as_slice() {
+        [syn::GenericArgument::Type(key_type), syn::GenericArgument::Type(value_type)]
+            if type_name(key_type).as_deref() == Some("String") =>
+        {
+            Some(value_type)
+        }
+        _ => None,
+    }
+}
+
+impl quote::ToTokens for DeriveInput {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = &self.name;
+        let comments = self.attributes.docs.lines();
+
+        let (values, sections): (Vec<_>, Vec<_>) = self
+            .fields
+            .iter()
+            .partition(|field| field.kind == FieldKind::Value);
+        let value_names = values.iter().map(|field| &field.name).collect::<Vec<_>>();
+        let value_keys = values.iter().map(|field| field.key()).collect::<Vec<_>>();
+        let value_comments = values
+            .iter()
+            .map(|field| field.docs.lines())
+            .collect::<Vec<_>>();
+        let value_types = values.iter().map(|field| field.type_description());
+        // Defaults that don't satisfy the constraints of their field are commented out
+        let value_commented = values.iter().map(|field| {
+            let ty = &field.ty;
+            let key = field.key();
+            let checks = field.constraints.runtime_checks(
+                &field.value_type(),
+                quote::quote! { &::toml_schema::__private::join(path, #key) },
+            );
+            if checks.is_empty() {
+                return quote::quote! { commented };
+            }
+            let name = &field.name;
+            quote::quote! {
+                commented || {
+                    let check = |value: #ty| -> ::core::result::Result<(), ::toml_schema::Error> {
+                        #checks
+                        ::core::result::Result::Ok(())
+                    };
+                    check(::core::clone::Clone::clone(&self.#name)).is_err()
+                }
+            }
+        });
+        let value_placeholders = values.iter().map(|field| {
+            placeholder(option_inner_type(&field.ty).unwrap_or(&field.ty))
+                .unwrap_or_else(|| "...".to_owned())
+        });
+        let write_sample_sections = sections.iter().map(|field| {
+            let name = &field.name;
+            let key = field.key();
+            let comments = field.docs.lines();
+            let function = match field.kind {
+                FieldKind::Table => quote::quote! { write_sample_table },
+                FieldKind::ArrayOfTables => quote::quote! { write_sample_array },
+                FieldKind::MapTable => quote::quote! { write_sample_map },
+                FieldKind::Value => unreachable!("values are not sections"),
+            };
+            quote::quote! {
+                ::toml_schema::__private::#function(
+                    out,
+                    path,
+                    #key,
+                    &[#(#comments),*],
+                    &self.#name,
+                    commented,
+                );
+            }
+        });
+
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                let name = &field.name;
+                let ty = &field.ty;
+                let key = field.key();
+                let checks = field.constraints.runtime_checks(
+                    &field.value_type(),
+                    quote::quote! { &::toml_schema::__private::join(path, #key) },
+                );
+                let integer_checks = field.constraints.integer_checks(
+                    &field.value_type(),
+                    quote::quote! { ::toml_schema::__private::integer(table.get(#key)) },
+                    quote::quote! { &::toml_schema::__private::join(path, #key) },
+                );
+                let read_integer_checks = field.constraints.integer_checks(
+                    &field.value_type(),
+                    quote::quote! { ::toml_schema::__private::integer_str(value) },
+                    quote::quote! { &field_path },
+                );
+                StructField {
+                    name: name.clone(),
+                    key: key.clone(),
+                    docs: field.docs.lines(),
+                    field_type: quote::quote! { #ty },
+                    is_section: field.kind != FieldKind::Value,
+                    is_table: matches!(field.kind, FieldKind::Table | FieldKind::MapTable),
+                    from_toml: quote::quote! {
+                        #name: {
+                            #integer_checks
+                            match ::toml_schema::__private::get::<#ty>(table, #key, path)? {
+                                ::core::option::Option::Some(value) => {
+                                    #checks
+                                    value
+                                }
+                                ::core::option::Option::None => defaults.#name,
+                            }
+                        }
+                    },
+                    read: quote::quote! {{
+                        #read_integer_checks
+                        ::toml_schema::__private::FromToml::from_toml_str(value, &field_path)?
+                    }},
+                    checks: field
+                        .constraints
+                        .runtime_checks(&field.value_type(), quote::quote! { &field_path }),
+                }
+            })
+            .collect();
+        let impls = StructImpls {
+            name,
+            fields,
+            attributes: &self.attributes,
+            before_from_toml: quote::quote! {
+                let defaults = <#name as ::core::default::Default>::default();
+            },
+        };
+
+        let getters = self.fields.iter().map(|field| {
+            let name = &field.name;
+            let docs = &field.docs;
+            let (return_type, value) = getter_type(&field.ty, quote::quote! { self.#name });
+            quote::quote! {
+                #docs
+                pub fn #name(&self) -> #return_type {
+                    #value
+                }
+            }
+        });
+
+        tokens.extend(quote::quote! {
+            #impls
+
+            impl ::toml_schema::__private::ToTomlSample for #name {
+                fn write_sample(&self, out: &mut String, path: &str, commented: bool) {
+                    #(
+                        ::toml_schema::__private::write_sample_key(
+                            out,
+                            #value_keys,
+                            &[#(#value_comments),*],
+                            #value_types,
+                            &self.#value_names,
+                            #value_placeholders,
+                            #value_commented,
+                        );
+                    )*
+                    #(
+                        #write_sample_sections
+                    )*
+                }
+            }
+
+            impl #name {
+                #(
+                    #getters
+                )*
+
+                /// An example of the config file, listing every table and key with its default value, type and doc
+                /// comments. Unset optional keys, and defaults that don't satisfy the constraints of their key, are
+                /// commented out.
+                pub fn sample_toml() -> &'static str {
+                    static SAMPLE: ::std::sync::OnceLock<String> = ::std::sync::OnceLock::new();
+                    SAMPLE.get_or_init(|| {
+                        ::toml_schema::__private::sample_document::<#name>(&[#(#comments),*])
+                    })
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,69 @@
+/// The doc comments given with `///` on a key or table. They are copied onto the generated fields, getters and
+/// structs, and written as `#` comments when the TOML is rendered.
+#[derive(Clone, Default)]
+pub(crate) struct Docs(Vec<syn::LitStr>);
+
+impl Docs {
+    /// Removes the doc comments from the given attributes, leaving the rest to be parsed.
+    pub fn take(attributes: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut docs = Docs::default();
+        let mut rest = Vec::new();
+        for attribute in attributes.drain(..) {
+            match &attribute.meta {
+                syn::Meta::NameValue(syn::MetaNameValue { path, value, .. })
+                    if path.is_ident("doc") =>
+                {
+                    docs.push(value.clone())?
+                }
+                _ => rest.push(attribute),
+            }
+        }
+        *attributes = rest;
+        Ok(docs)
+    }
+
+    /// Adds the value of a `doc` attribute, like `#[doc = " Suppress all output"]`.
+    pub fn push(&mut self, value: syn::Expr) -> syn::Result<()> {
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(line),
+            ..
+        }) = value
+        else {
+            return Err(syn::Error::new_spanned(value, "Expected a string"));
+        };
+        self.0.push(line);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn extend(&mut self, docs: Docs) {
+        self.0.extend(docs.0);
+    }
+
+    /// The lines of the doc comments without the space after `///`, as they are written after `# `.
+    pub fn lines(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|line| {
+                line.value()
+                    .split('\n')
+                    .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl quote::ToTokens for Docs {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let lines = &self.0;
+        tokens.extend(quote::quote! {
+            #(
+                #[doc = #lines]
+            )*
+        });
+    }
+}
@@ -0,0 +1,214 @@
+use syn::bracketed;
+
+use crate::{
+    docs::Docs,
+    item::Item,
+    json_schema::document_schema,
+    sample::Sample,
+    table::Table,
+    table_attributes::TableAttributes,
+    toml_struct::TomlStruct,
+    type_name::{Names, TypeName},
+};
+
+pub struct TomlDocument {
+    items: Vec<Item>,
+    metadata: DocumentMetadata,
+}
+
+impl syn::parse::Parse for TomlDocument {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Metadata, after any doc comments for the generated struct
+        let mut docs = Docs::default();
+        let mut metadata: DocumentMetadata = loop {
+            input.parse::<syn::Token![#]>()?;
+            let meta;
+            bracketed!(meta in input);
+            if meta.peek(syn::Ident) && meta.fork().parse::<syn::Ident>()? == "doc" {
+                docs.push(meta.parse::<syn::MetaNameValue>()?.value)?;
+                continue;
+            }
+            break meta.parse()?;
+        };
+        metadata.attributes.docs = docs;
+
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            let item: Item = input.parse()?;
+            insert_item(&mut items, item)?;
+        }
+        for item in &mut items {
+            item.inherit_attributes(&metadata.attributes);
+        }
+        Names::check_document(&metadata.type_name(), &items)?;
+        Ok(TomlDocument { items, metadata })
+    }
+}
+
+impl quote::ToTokens for TomlDocument {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let parent_name = self.metadata.type_name();
+
+        let items = self
+            .items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_child_item(parent_name.clone()))
+            .collect::<Vec<_>>();
+
+        let prelude = items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_prelude())
+            .collect::<Vec<_>>();
+        let prelude = parent_name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        let toml_struct = TomlStruct {
+            name: parent_name.clone(),
+            items,
+            attributes: self.metadata.attributes.clone(),
+        };
+
+        let sample = Sample::render(&self.metadata.attributes.docs, &self.items);
+        let json_schema = document_schema(
+            &self.metadata.name,
+            &self.metadata.attributes.docs,
+            &self.items,
+        );
+        let name = &self.metadata.name;
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #toml_struct
+
+            impl #name {
+                /// An example of the config file, listing every table and key with its default value, type and doc
+                /// comments. Optional keys are commented out.
+                pub fn sample_toml() -> &'static str {
+                    #sample
+                }
+
+                /// A JSON Schema describing the config file, for editors that validate and complete TOML against
+                /// one, such as Taplo.
+                pub fn json_schema() -> &'static str {
+                    #json_schema
+                }
+            }
+        });
+    }
+}
+
+/// The metadata at the start of the document, like `#[name = Config, validate = my_crate::check, derive(Debug)]`.
+/// Apart from the name and `modules`, it takes the same attributes as a table.
+struct DocumentMetadata {
+    name: syn::Ident,
+    attributes: TableAttributes,
+
+    /// Whether the types of the tables are defined in a module tree named after the document, like
+    /// `config::options::Output`, rather than named like `ConfigOptionsOutput`.
+    modules: bool,
+}
+
+impl DocumentMetadata {
+    fn type_name(&self) -> TypeName {
+        TypeName::root(self.name.clone(), self.modules)
+    }
+}
+
+impl syn::parse::Parse for DocumentMetadata {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let key_token = input.parse::<syn::Ident>()?;
+        if key_token != "name" {
+            return Err(input.error("Expected name"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let name = input.parse()?;
+
+        let mut attributes = TableAttributes::default();
+        let mut modules = false;
+        while input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            match input.parse()? {
+                syn::Meta::Path(path) if path.is_ident("modules") => modules = true,
+                meta => attributes.set_meta(meta)?,
+            }
+        }
+        Ok(DocumentMetadata {
+            name,
+            attributes,
+            modules,
+        })
+    }
+}
+
+/// Inserts an item into the items of a table, resolving the super-tables of a dotted heading and merging repeated
+/// declarations of the same table or array of tables.
+pub(crate) fn insert_item(items: &mut Vec<Item>, mut item: Item) -> syn::Result<()> {
+    let supertables = match &mut item {
+        Item::Table(table) => std::mem::take(&mut table.supertables),
+        Item::ArrayOfTables(array) => std::mem::take(&mut array.supertables),
+        Item::MapTable(map) => std::mem::take(&mut map.supertables),
+        Item::Property(_) => Vec::new(),
+    };
+
+    let mut items = items;
+    for supertable in supertables {
+        items = supertable_items(items, supertable)?;
+    }
+
+    let existing = items
+        .iter_mut()
+        .find(|existing| existing.name() == item.name());
+    match (existing, item) {
+        (None, Item::Table(table)) => {
+            let mut declared = Table::implicit(table.heading().clone());
+            declared.merge(table)?;
+            items.push(Item::Table(declared));
+        }
+        (None, item) => items.push(item),
+        (Some(Item::Table(existing)), Item::Table(table)) => existing.merge(table)?,
+        (Some(Item::ArrayOfTables(existing)), Item::ArrayOfTables(array)) => {
+            existing.add_entry(array)?
+        }
+        (Some(_), item) => {
+            return Err(syn::Error::new(
+                item.name().span(),
+                format!("`{}` is already defined", item.name()),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// The items of the super-table with the given name, creating it if it has not been declared yet.
+fn supertable_items(items: &mut Vec<Item>, name: syn::Ident) -> syn::Result<&mut Vec<Item>> {
+    let index = match items.iter().position(|item| item.name() == &name) {
+        Some(index) => index,
+        None => {
+            items.push(Item::Table(Table::implicit(name.clone())));
+            items.len() - 1
+        }
+    };
+
+    match &mut items[index] {
+        Item::Table(table) => Ok(table.items_mut()),
+        Item::ArrayOfTables(array) => Ok(array.items_mut()),
+        Item::MapTable(_) => Err(syn::Error::new(
+            name.span(),
+            format!("`{name}` has user-chosen keys, so its entries cannot be declared"),
+        )),
+        Item::Property(_) => Err(syn::Error::new(
+            name.span(),
+            format!("`{name}` is not a table"),
+        )),
+    }
+}
@@ -0,0 +1,224 @@
+use syn::spanned::Spanned as _;
+
+use crate::{table_attributes::TableAttributes, type_name::TypeName, variant_name};
+
+/// A string constrained to a fixed set of values, written as `"JSON" | "YAML" | "TOML"`, which generates an enum.
+#[derive(Clone)]
+pub(crate) struct Enumeration {
+    pub values: Vec<syn::LitStr>,
+
+    /// The index of the value this defaults to, which is the first one unless it is overridden.
+    pub selected: usize,
+
+    /// The derives and visibility of the enclosing table, which the generated enum takes on top of the derives it
+    /// always has.
+    pub attributes: TableAttributes,
+}
+
+impl Enumeration {
+    /// Creates an enumeration from an expression of string literals separated by `|`.
+    pub fn new(expr: syn::Expr) -> syn::Result<Self> {
+        let mut values = Vec::new();
+        collect_values(expr, &mut values)?;
+
+        let mut variants = Vec::<(syn::Ident, &syn::LitStr)>::new();
+        for value in &values {
+            let variant = variant_name(value)?;
+            if let Some((_, existing)) = variants.iter().find(|(existing, _)| existing == &variant)
+            {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!(
+                        "`{}` has the same variant name `{variant}` as `{}`",
+                        value.value(),
+                        existing.value()
+                    ),
+                ));
+            }
+            variants.push((variant, value));
+        }
+
+        Ok(Enumeration {
+            values,
+            selected: 0,
+            attributes: TableAttributes::default(),
+        })
+    }
+
+    /// The names of the generated variants, in the order of the values.
+    pub fn variants(&self) -> Vec<syn::Ident> {
+        self.values
+            .iter()
+            .map(|value| variant_name(value).unwrap())
+            .collect()
+    }
+
+    /// Selects the value given by a string literal, such as when it overrides the default declared elsewhere in
+    /// the schema.
+    pub fn select(&self, value: &syn::LitStr) -> syn::Result<Self> {
+        let Some(selected) = self
+            .values
+            .iter()
+            .position(|allowed| allowed.value() == value.value())
+        else {
+            return Err(syn::Error::new(value.span(), self.expected()));
+        };
+        Ok(Enumeration {
+            selected,
+            ..self.clone()
+        })
+    }
+
+    /// A message listing the allowed values.
+    pub fn expected(&self) -> String {
+        format!(
+            "Expected one of {}",
+            self.values
+                .iter()
+                .map(|value| format!("`{}`", value.value()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+fn collect_values(expr: syn::Expr, values: &mut Vec<syn::LitStr>) -> syn::Result<()> {
+    match expr {
+        syn::Expr::Binary(syn::ExprBinary {
+            left,
+            op: syn::BinOp::BitOr(_),
+            right,
+            ..
+        }) => {
+            collect_values(*left, values)?;
+            collect_values(*right, values)
+        }
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) => {
+            if values
+                .iter()
+                .any(|existing| existing.value() == value.value())
+            {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!("`{}` is listed more than once", value.value()),
+                ));
+            }
+            values.push(value);
+            Ok(())
+        }
+        expr => Err(syn::Error::new(
+            expr.span(),
+            "Only strings can be listed as the values of an enum",
+        )),
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining the enum generated for
+/// an enumeration, along with the runtime support implemented on it.
+pub struct EnumerationPrelude {
+    pub enumeration: Enumeration,
+    pub name: TypeName,
+}
+
+impl quote::ToTokens for EnumerationPrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = &self.name.ident;
+        let values = &self.enumeration.values;
+        let variants = self.enumeration.variants();
+        let default = &variants[0];
+        let visibility = self
+            .name
+            .visibility(&self.enumeration.attributes.visibility());
+        let derives = self
+            .enumeration
+            .attributes
+            .derives()
+            .iter()
+            .filter(|derive| {
+                !["Debug", "Clone", "Copy", "PartialEq", "Eq"]
+                    .iter()
+                    .any(|builtin| derive.is_ident(builtin))
+            });
+
+        tokens.extend(quote::quote! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq #(, #derives)*)]
+            #visibility enum #name {
+                #(
+                    #variants
+                ),*
+            }
+
+            impl #name {
+                /// The values of this enum as they are written in TOML.
+                pub const VALUES: &'static [&'static str] = &[#(#values),*];
+
+                /// This value as it is written in TOML.
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        #(
+                            #name::#variants => #values
+                        ),*
+                    }
+                }
+
+                fn from_value(value: &str, path: &str) -> ::core::result::Result<Self, ::toml_schema::Error> {
+                    match value {
+                        #(
+                            #values => ::core::result::Result::Ok(#name::#variants),
+                        )*
+                        _ => ::core::result::Result::Err(::toml_schema::Error::InvalidVariant {
+                            path: path.to_owned(),
+                            value: value.to_owned(),
+                            expected: #name::VALUES,
+                        }),
+                    }
+                }
+            }
+
+            impl ::core::default::Default for #name {
+                fn default() -> Self {
+                    #name::#default
+                }
+            }
+
+            impl ::toml_schema::__private::FromToml for #name {
+                fn from_toml(
+                    value: &::toml_schema::__private::Value,
+                    path: &str,
+                ) -> ::core::result::Result<Self, ::toml_schema::Error> {
+                    let value: String = ::toml_schema::__private::FromToml::from_toml(value, path)?;
+                    #name::from_value(&value, path)
+                }
+
+                /// The value can be given as raw text or as a quoted TOML string, like other strings.
+                fn from_toml_str(source: &str, path: &str) -> ::core::result::Result<Self, ::toml_schema::Error> {
+                    let value: String = ::toml_schema::__private::FromToml::from_toml_str(source, path)?;
+                    #name::from_value(&value, path)
+                }
+            }
+
+            impl ::core::str::FromStr for #name {
+                type Err = ::toml_schema::Error;
+
+                fn from_str(source: &str) -> ::core::result::Result<Self, Self::Err> {
+                    #name::from_value(source, "")
+                }
+            }
+
+            impl ::toml_schema::__private::ToToml for #name {
+                fn write_inline(&self, out: &mut String) {
+                    ::toml_schema::__private::ToToml::write_inline(&self.as_str().to_owned(), out);
+                }
+            }
+
+            impl ::core::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    item::ChildItem,
+    property::{ChildPropertyDefault, Property},
+    table_attributes::TableAttributes,
+    toml_struct::TomlStruct,
+    type_name::TypeName,
+    Value,
+};
+
+#[derive(Clone)]
+pub(crate) struct InlineTable {
+    pub properties: Vec<Property>,
+
+    /// Whether this table was created by a dotted key such as `output.format = "JSON"`, rather than written out
+    /// in braces. Only tables created by dotted keys can be extended by further dotted keys.
+    pub dotted: bool,
+
+    /// The derives and other attributes of the generated struct, which are those of the enclosing table.
+    pub attributes: TableAttributes,
+}
+
+impl syn::parse::Parse for InlineTable {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut properties = Vec::new();
+        let mut ate_last_comma = true;
+        while input.peek(syn::Ident) || input.peek(syn::Token![#]) {
+            if !ate_last_comma {
+                input.parse::<syn::Token![,]>()?;
+            }
+            insert_property(&mut properties, input.parse()?)?;
+            if input.peek(syn::token::Comma) {
+                input.parse::<syn::Token![,]>()?;
+                ate_last_comma = true;
+            }
+        }
+
+        Ok(InlineTable {
+            properties,
+            dotted: false,
+            attributes: TableAttributes::default(),
+        })
+    }
+}
+
+/// Adds a property to the properties of an inline table, merging tables created by dotted keys with the same
+/// name.
+pub(crate) fn insert_property(
+    properties: &mut Vec<Property>,
+    property: Property,
+) -> syn::Result<()> {
+    let Some(existing) = properties
+        .iter_mut()
+        .find(|existing| existing.name == property.name)
+    else {
+        properties.push(property);
+        return Ok(());
+    };
+
+    match (&mut existing.value, property.value) {
+        (Some(Value::InlineTable(existing)), Some(Value::InlineTable(table)))
+            if existing.dotted && table.dotted =>
+        {
+            for property in table.properties {
+                insert_property(&mut existing.properties, property)?;
+            }
+            Ok(())
+        }
+        _ => Err(syn::Error::new(
+            property.name.span(),
+            format!("`{}` is already defined", property.name),
+        )),
+    }
+}
+
+impl InlineTable {
+    pub fn into_child_inline_table(
+        self,
+        name: syn::Ident,
+        parent_name: TypeName,
+    ) -> ChildInlineTable {
+        ChildInlineTable {
+            table: self,
+            parent_name,
+            name,
+        }
+    }
+}
+
+pub struct ChildInlineTable {
+    table: InlineTable,
+    parent_name: TypeName,
+    name: syn::Ident,
+}
+
+impl ChildInlineTable {
+    pub fn type_name(&self) -> TypeName {
+        self.parent_name.child(&self.name)
+    }
+
+    /// The properties of this inline table, as items of the struct generated for it.
+    pub fn items(&self) -> Vec<ChildItem> {
+        let heading_type = self.type_name();
+        self.table
+            .properties
+            .iter()
+            .map(|property| {
+                ChildItem::Property(property.clone().into_child_property(heading_type.clone()))
+            })
+            .collect()
+    }
+}
+
+impl quote::ToTokens for ChildInlineTable {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(quote::quote! {});
+    }
+}
+
+pub struct ChildInlineTablePrelude(pub ChildInlineTable);
+
+impl std::ops::Deref for ChildInlineTablePrelude {
+    type Target = ChildInlineTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildInlineTablePrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let items = self.items();
+
+        let name = self.type_name();
+        let prelude = items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_prelude())
+            .collect::<Vec<_>>();
+        let prelude = name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        let toml_struct = TomlStruct {
+            name,
+            items,
+            attributes: self.table.attributes.clone(),
+        };
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #toml_struct
+        });
+    }
+}
+
+pub struct ChildInlineTableDefault(pub ChildInlineTable);
+
+impl std::ops::Deref for ChildInlineTableDefault {
+    type Target = ChildInlineTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildInlineTableDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading_type = self.type_name();
+
+        let properties = self
+            .table
+            .properties
+            .iter()
+            .map(|property| {
+                ChildPropertyDefault(property.clone().into_child_property(heading_type.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        tokens.extend(quote::quote! {
+            #heading_type {
+                #(
+                    #properties
+                ),*
+            }
+        });
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{
+    array_of_tables::{
+        ArrayOfTables, ChildArrayOfTables, ChildArrayOfTablesDefault, ChildArrayOfTablesFromToml,
+        ChildArrayOfTablesGetter, ChildArrayOfTablesPrelude,
+    },
+    docs::Docs,
+    map_table::{
+        ChildMapTable, ChildMapTableDefault, ChildMapTableFromToml, ChildMapTableGetter,
+        ChildMapTablePrelude, MapTable,
+    },
+    property::{
+        ChildProperty, ChildPropertyDefault, ChildPropertyFromToml, ChildPropertyGetter,
+        ChildPropertyPrelude, Property,
+    },
+    table::{
+        ChildTable, ChildTableDefault, ChildTableFromToml, ChildTableGetter, ChildTablePrelude,
+        Table,
+    },
+    table_attributes::TableAttributes,
+    type_name::TypeName,
+    Value,
+};
+
+#[derive(Clone)]
+pub(crate) enum Item {
+    Table(Table),
+    ArrayOfTables(ArrayOfTables),
+    MapTable(MapTable),
+    Property(Property),
+}
+
+impl syn::parse::Parse for Item {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Attributes before a heading belong to the table, and otherwise to the property that follows them
+        let fork = input.fork();
+        fork.call(syn::Attribute::parse_outer)?;
+        if fork.peek(syn::token::Bracket) {
+            let attributes =
+                TableAttributes::from_attributes(input.call(syn::Attribute::parse_outer)?)?;
+
+            if crate::map_table::is_map_heading(input) {
+                let mut map: MapTable = input.parse()?;
+                map.attributes = attributes;
+                return Ok(Item::MapTable(map));
+            }
+
+            let fork = input.fork();
+            let heading;
+            syn::bracketed!(heading in fork);
+            if heading.peek(syn::token::Bracket) {
+                let mut array: ArrayOfTables = input.parse()?;
+                array.attributes = attributes;
+                return Ok(Item::ArrayOfTables(array));
+            }
+
+            let mut table: Table = input.parse()?;
+            table.attributes = attributes;
+            return Ok(Item::Table(table));
+        }
+
+        if input.peek(syn::Ident) || input.peek(syn::Token![#]) {
+            let property: Property = input.parse()?;
+            input.parse::<syn::Token![;]>()?;
+            return Ok(Item::from(property));
+        }
+
+        Err(input.error("Invalid TOML syntax"))
+    }
+}
+
+impl From<Property> for Item {
+    /// Tables created by dotted keys are written as `[sections]` like any other table outside of an inline table.
+    fn from(property: Property) -> Self {
+        match property {
+            Property {
+                name,
+                value: Some(Value::InlineTable(table)),
+                ..
+            } if table.dotted => Item::Table(Table::dotted(
+                name,
+                table.properties.into_iter().map(Item::from).collect(),
+            )),
+            property => Item::Property(property),
+        }
+    }
+}
+
+impl Item {
+    pub fn name(&self) -> &syn::Ident {
+        match self {
+            Item::Table(table) => table.heading(),
+            Item::ArrayOfTables(array) => array.heading(),
+            Item::MapTable(map) => map.heading(),
+            Item::Property(property) => &property.name,
+        }
+    }
+
+    pub fn has_default(&self) -> bool {
+        match self {
+            Item::Table(table) => table.has_default(),
+            Item::ArrayOfTables(_) | Item::MapTable(_) => true,
+            Item::Property(property) => property.has_default(),
+        }
+    }
+
+    /// Gives the types generated for this item the derives, other attributes and visibilities of the enclosing
+    /// table, unless it is a table that gives its own.
+    pub fn inherit_attributes(&mut self, parent: &TableAttributes) {
+        let attributes = match self {
+            Item::Table(table) => &mut table.attributes,
+            Item::ArrayOfTables(array) => &mut array.attributes,
+            Item::MapTable(map) => &mut map.attributes,
+            Item::Property(property) => return property.inherit_attributes(parent),
+        };
+        attributes.inherit(parent);
+        let attributes = attributes.clone();
+
+        let items = match self {
+            Item::Table(table) => table.items_mut(),
+            Item::ArrayOfTables(array) => array.items_mut(),
+            Item::MapTable(map) => map.items_mut(),
+            Item::Property(_) => unreachable!("properties are handled above"),
+        };
+        for item in items {
+            item.inherit_attributes(&attributes);
+        }
+    }
+
+    pub fn into_child_item(self, parent_name: TypeName) -> ChildItem {
+        match self {
+            Item::Table(table) => ChildItem::Table(table.into_child_table(parent_name)),
+            Item::ArrayOfTables(array) => {
+                ChildItem::ArrayOfTables(array.into_child_array_of_tables(parent_name))
+            }
+            Item::MapTable(map) => ChildItem::MapTable(map.into_child_map_table(parent_name)),
+            Item::Property(property) => {
+                ChildItem::Property(property.into_child_property(parent_name))
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ChildItem {
+    Table(ChildTable),
+    ArrayOfTables(ChildArrayOfTables),
+    MapTable(ChildMapTable),
+    Property(ChildProperty),
+}
+
+impl quote::ToTokens for ChildItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ChildItem::Table(table) => table.to_tokens(tokens),
+            ChildItem::ArrayOfTables(array) => array.to_tokens(tokens),
+            ChildItem::MapTable(map) => map.to_tokens(tokens),
+            ChildItem::Property(property) => property.to_tokens(tokens),
+        }
+    }
+}
+
+impl ChildItem {
+    pub fn name(&self) -> &syn::Ident {
+        match self {
+            ChildItem::Table(table) => table.name(),
+            ChildItem::ArrayOfTables(array) => array.name(),
+            ChildItem::MapTable(map) => map.name(),
+            ChildItem::Property(property) => &property.property.name,
+        }
+    }
+
+    pub fn docs(&self) -> &Docs {
+        match self {
+            ChildItem::Table(table) => table.docs(),
+            ChildItem::ArrayOfTables(array) => array.docs(),
+            ChildItem::MapTable(map) => map.docs(),
+            ChildItem::Property(property) => &property.property.docs,
+        }
+    }
+
+    pub fn has_default(&self) -> bool {
+        match self {
+            ChildItem::Table(table) => table.has_default(),
+            ChildItem::ArrayOfTables(_) | ChildItem::MapTable(_) => true,
+            ChildItem::Property(property) => property.property.has_default(),
+        }
+    }
+
+    pub fn field_type(&self) -> proc_macro2::TokenStream {
+        match self {
+            ChildItem::Table(table) => {
+                let type_name = table.type_name();
+                quote::quote! { #type_name }
+            }
+            ChildItem::ArrayOfTables(array) => {
+                let element_type = array.type_name();
+                quote::quote! { ::std::vec::Vec<#element_type> }
+            }
+            ChildItem::MapTable(map) => {
+                let entry_type = map.type_name();
+                quote::quote! { ::std::collections::BTreeMap<String, #entry_type> }
+            }
+            ChildItem::Property(property) => property.field_type(),
+        }
+    }
+
+    /// The statements that check the constraints of this item against the value in the variable `value`,
+    /// reporting errors for the key at `path`.
+    pub fn runtime_checks(&self, path: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            ChildItem::Property(property) => property.runtime_checks(path),
+            _ => quote::quote! {},
+        }
+    }
+
+    /// The statements that check the integer in the expression `integer`, an `Option<i128>`, against the range
+    /// of this item before it is converted to the type of the item.
+    pub fn integer_checks(
+        &self,
+        integer: proc_macro2::TokenStream,
+        path: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            ChildItem::Property(property) => property.integer_checks(integer, path),
+            _ => quote::quote! {},
+        }
+    }
+
+    /// Whether this item holds keys that `try_set` can set through it, like a nested struct.
+    pub fn is_table(&self) -> bool {
+        match self {
+            ChildItem::Table(_) | ChildItem::MapTable(_) => true,
+            ChildItem::ArrayOfTables(_) => false,
+            ChildItem::Property(property) => {
+                matches!(
+                    property.property.value,
+                    Some(Value::InlineTable(_) | Value::Union(_))
+                )
+            }
+        }
+    }
+
+    /// Whether this item is written as a `[section]` rather than as a key-value pair.
+    pub fn is_section(&self) -> bool {
+        matches!(
+            self,
+            ChildItem::Table(_) | ChildItem::ArrayOfTables(_) | ChildItem::MapTable(_)
+        )
+    }
+
+    pub fn into_item_prelude(self) -> ChildItemPrelude {
+        match self {
+            ChildItem::Table(table) => ChildItemPrelude::Table(ChildTablePrelude(table)),
+            ChildItem::ArrayOfTables(array) => {
+                ChildItemPrelude::ArrayOfTables(ChildArrayOfTablesPrelude(array))
+            }
+            ChildItem::MapTable(map) => ChildItemPrelude::MapTable(ChildMapTablePrelude(map)),
+            ChildItem::Property(property) => {
+                ChildItemPrelude::Property(ChildPropertyPrelude(property))
+            }
+        }
+    }
+
+    pub fn into_item_default(self) -> ChildItemDefault {
+        match self {
+            ChildItem::Table(table) => ChildItemDefault::Table(ChildTableDefault(table)),
+            ChildItem::ArrayOfTables(array) => {
+                ChildItemDefault::ArrayOfTables(ChildArrayOfTablesDefault(array))
+            }
+            ChildItem::MapTable(map) => ChildItemDefault::MapTable(ChildMapTableDefault(map)),
+            ChildItem::Property(property) => {
+                ChildItemDefault::Property(ChildPropertyDefault(property))
+            }
+        }
+    }
+
+    pub fn into_item_from_toml(self) -> ChildItemFromToml {
+        match self {
+            ChildItem::Table(table) => ChildItemFromToml::Table(ChildTableFromToml(table)),
+            ChildItem::ArrayOfTables(array) => {
+                ChildItemFromToml::ArrayOfTables(ChildArrayOfTablesFromToml(array))
+            }
+            ChildItem::MapTable(map) => ChildItemFromToml::MapTable(ChildMapTableFromToml(map)),
+            ChildItem::Property(property) => {
+                ChildItemFromToml::Property(ChildPropertyFromToml(property))
+            }
+        }
+    }
+
+    pub fn into_item_getter(self) -> ChildItemGetter {
+        match self {
+            ChildItem::Table(table) => ChildItemGetter::Table(ChildTableGetter(table)),
+            ChildItem::ArrayOfTables(array) => {
+                ChildItemGetter::ArrayOfTables(ChildArrayOfTablesGetter(array))
+            }
+            ChildItem::MapTable(map) => ChildItemGetter::MapTable(ChildMapTableGetter(map)),
+            ChildItem::Property(property) => {
+                ChildItemGetter::Property(ChildPropertyGetter(property))
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ChildItemPrelude {
+    Table(ChildTablePrelude),
+    ArrayOfTables(ChildArrayOfTablesPrelude),
+    MapTable(ChildMapTablePrelude),
+    Property(ChildPropertyPrelude),
+}
+
+impl quote::ToTokens for ChildItemPrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ChildItemPrelude::Table(table) => table.to_tokens(tokens),
+            ChildItemPrelude::ArrayOfTables(array) => array.to_tokens(tokens),
+            ChildItemPrelude::MapTable(map) => map.to_tokens(tokens),
+            ChildItemPrelude::Property(property) => property.to_tokens(tokens),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ChildItemDefault {
+    Table(ChildTableDefault),
+    ArrayOfTables(ChildArrayOfTablesDefault),
+    MapTable(ChildMapTableDefault),
+    Property(ChildPropertyDefault),
+}
+
+impl quote::ToTokens for ChildItemDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ChildItemDefault::Table(table) => table.to_tokens(tokens),
+            ChildItemDefault::ArrayOfTables(array) => array.to_tokens(tokens),
+            ChildItemDefault::MapTable(map) => map.to_tokens(tokens),
+            ChildItemDefault::Property(property) => property.to_tokens(tokens),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ChildItemFromToml {
+    Table(ChildTableFromToml),
+    ArrayOfTables(ChildArrayOfTablesFromToml),
+    MapTable(ChildMapTableFromToml),
+    Property(ChildPropertyFromToml),
+}
+
+impl quote::ToTokens for ChildItemFromToml {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ChildItemFromToml::Table(table) => table.to_tokens(tokens),
+            ChildItemFromToml::ArrayOfTables(array) => array.to_tokens(tokens),
+            ChildItemFromToml::MapTable(map) => map.to_tokens(tokens),
+            ChildItemFromToml::Property(property) => property.to_tokens(tokens),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ChildItemGetter {
+    Table(ChildTableGetter),
+    ArrayOfTables(ChildArrayOfTablesGetter),
+    MapTable(ChildMapTableGetter),
+    Property(ChildPropertyGetter),
+}
+
+impl quote::ToTokens for ChildItemGetter {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ChildItemGetter::Table(table) => table.to_tokens(tokens),
+            ChildItemGetter::ArrayOfTables(array) => array.to_tokens(tokens),
+            ChildItemGetter::MapTable(map) => map.to_tokens(tokens),
+            ChildItemGetter::Property(property) => property.to_tokens(tokens),
+        }
+    }
+}
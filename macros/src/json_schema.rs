@@ -0,0 +1,232 @@
+use serde_json::{json, Map, Value as Json};
+use syn::ext::IdentExt as _;
+
+use crate::{
+    docs::Docs, item::Item, option_inner_type, property::Property, type_name, vec_element_type,
+    Value,
+};
+
+/// Builds the JSON Schema returned by `json_schema`, which editors such as Taplo use to validate and complete
+/// the config file.
+pub(crate) fn document_schema(name: &syn::Ident, docs: &Docs, items: &[Item]) -> String {
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_owned(),
+        "http://json-schema.org/draft-07/schema#".into(),
+    );
+    schema.insert("title".to_owned(), name.to_string().into());
+    schema.extend(table_schema(docs, items));
+    serde_json::to_string_pretty(&schema).unwrap()
+}
+
+fn table_schema(docs: &Docs, items: &[Item]) -> Map<String, Json> {
+    let keys = items
+        .iter()
+        .map(|item| (item.name(), item_schema(item), item.has_default()));
+    let mut schema = object_schema(keys);
+    describe(&mut schema, docs);
+    schema
+}
+
+/// The schema of a table with the given keys, along with the schema of each and whether it has a default.
+fn object_schema<'a>(
+    keys: impl IntoIterator<Item = (&'a syn::Ident, Map<String, Json>, bool)>,
+) -> Map<String, Json> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, schema, has_default) in keys {
+        let key = name.unraw().to_string();
+        if !has_default {
+            required.push(Json::from(key.clone()));
+        }
+        properties.insert(key, schema.into());
+    }
+
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), "object".into());
+    schema.insert("properties".to_owned(), properties.into());
+    if !required.is_empty() {
+        schema.insert("required".to_owned(), required.into());
+    }
+    schema.insert("additionalProperties".to_owned(), false.into());
+    schema
+}
+
+fn item_schema(item: &Item) -> Map<String, Json> {
+    match item {
+        Item::Table(table) => table_schema(&table.attributes.docs, table.items()),
+        Item::ArrayOfTables(array) => {
+            let mut schema = Map::new();
+            describe(&mut schema, &array.attributes.docs);
+            schema.insert("type".to_owned(), "array".into());
+            schema.insert(
+                "items".to_owned(),
+                table_schema(&Docs::default(), array.items()).into(),
+            );
+            schema
+        }
+        Item::MapTable(map) => {
+            let mut schema = Map::new();
+            describe(&mut schema, &map.attributes.docs);
+            schema.insert("type".to_owned(), "object".into());
+            schema.insert(
+                "additionalProperties".to_owned(),
+                table_schema(&Docs::default(), map.items()).into(),
+            );
+            schema
+        }
+        Item::Property(property) => property_schema(property),
+    }
+}
+
+fn property_schema(property: &Property) -> Map<String, Json> {
+    let explicit_type = property
+        .explicit_type
+        .as_deref()
+        .map(|explicit_type| option_inner_type(explicit_type).unwrap_or(explicit_type));
+    let mut schema = explicit_type
+        .and_then(type_schema)
+        .or_else(|| property.value.as_ref().map(value_schema))
+        .unwrap_or_default();
+
+    describe(&mut schema, &property.docs);
+    schema.extend(property.constraints.json_schema());
+    if let Some(default) = property.value.as_ref().and_then(default_value) {
+        schema.insert("default".to_owned(), default);
+    }
+    schema
+}
+
+/// The schema of a value, for keys whose type is inferred from their default.
+fn value_schema(value: &Value) -> Map<String, Json> {
+    let schema = match value {
+        Value::Boolean(_) => json!({ "type": "boolean" }),
+        Value::Integer(_) => json!({ "type": "integer" }),
+        Value::Float(_) => json!({ "type": "number" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(array) => {
+            let items = type_schema(&array.element_type)
+                .or_else(|| array.elements.first().map(value_schema))
+                .unwrap_or_default();
+            json!({ "type": "array", "items": items })
+        }
+        Value::Enum(enumeration) => {
+            let values = enumeration
+                .values
+                .iter()
+                .map(syn::LitStr::value)
+                .collect::<Vec<_>>();
+            json!({ "type": "string", "enum": values })
+        }
+        Value::InlineTable(table) => return properties_schema(&table.properties),
+        Value::Union(union) => {
+            let alternatives = union
+                .alternatives
+                .iter()
+                .map(|alternative| {
+                    let tag = (
+                        &union.tag,
+                        json!({ "const": alternative.tag_value.value() })
+                            .as_object()
+                            .unwrap()
+                            .clone(),
+                        false,
+                    );
+                    let keys = alternative.properties.iter().map(|property| {
+                        (
+                            &property.name,
+                            property_schema(property),
+                            property.has_default(),
+                        )
+                    });
+                    object_schema(std::iter::once(tag).chain(keys)).into()
+                })
+                .collect::<Vec<Json>>();
+            json!({ "oneOf": alternatives })
+        }
+    };
+    schema.as_object().unwrap().clone()
+}
+
+fn properties_schema(properties: &[Property]) -> Map<String, Json> {
+    object_schema(properties.iter().map(|property| {
+        (
+            &property.name,
+            property_schema(property),
+            property.has_default(),
+        )
+    }))
+}
+
+/// The schema of an explicitly given type, if it is one that maps onto a JSON type.
+fn type_schema(ty: &syn::Type) -> Option<Map<String, Json>> {
+    if let Some(element_type) = vec_element_type(ty) {
+        let items = type_schema(element_type).unwrap_or_default();
+        return json!({ "type": "array", "items": items })
+            .as_object()
+            .cloned();
+    }
+    let schema = match type_name(ty)?.as_str() {
+        "bool" => json!({ "type": "boolean" }),
+        "u8" | "u16" | "u32" | "u64" | "usize" => json!({ "type": "integer", "minimum": 0 }),
+        "i8" | "i16" | "i32" | "i64" | "isize" => json!({ "type": "integer" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "String" | "PathBuf" => json!({ "type": "string" }),
+        _ => return None,
+    };
+    schema.as_object().cloned()
+}
+
+/// The default value in JSON, which is left out for keys that don't have one.
+fn default_value(value: &Value) -> Option<Json> {
+    Some(match value {
+        Value::Boolean(value) => (*value).into(),
+        Value::Integer(value) => {
+            let digits = value.base10_digits();
+            match (digits.parse::<i64>(), digits.parse::<u64>()) {
+                (Ok(value), _) => value.into(),
+                (_, Ok(value)) => value.into(),
+                _ => return None,
+            }
+        }
+        Value::Float(value) => value.base10_digits().parse::<f64>().ok()?.into(),
+        Value::String(value) => value.value().into(),
+        Value::Array(array) => array
+            .elements
+            .iter()
+            .map(default_value)
+            .collect::<Option<Vec<_>>>()?
+            .into(),
+        Value::Enum(enumeration) => enumeration.values[enumeration.selected].value().into(),
+        Value::InlineTable(table) => properties_default(None, &table.properties)?,
+        Value::Union(union) => {
+            let alternative = &union.alternatives[0];
+            let tag = (
+                union.tag.unraw().to_string(),
+                Json::from(alternative.tag_value.value()),
+            );
+            properties_default(Some(tag), &alternative.properties)?
+        }
+    })
+}
+
+/// The default of an inline table, which only exists if every key of it that isn't optional has one.
+fn properties_default(first: Option<(String, Json)>, properties: &[Property]) -> Option<Json> {
+    let mut table = Map::from_iter(first);
+    for property in properties {
+        match &property.value {
+            Some(value) => {
+                table.insert(property.name.unraw().to_string(), default_value(value)?);
+            }
+            None if property.is_optional() => {}
+            None => return None,
+        }
+    }
+    Some(table.into())
+}
+
+fn describe(schema: &mut Map<String, Json>, docs: &Docs) {
+    if !docs.is_empty() {
+        schema.insert("description".to_owned(), docs.lines().join("\n").into());
+    }
+}
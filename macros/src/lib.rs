@@ -0,0 +1,458 @@
+use array::Array;
+use convert_case::Casing as _;
+use document::TomlDocument;
+use enumeration::Enumeration;
+use inline_table::{ChildInlineTableDefault, InlineTable};
+use proc_macro::TokenStream;
+use property::Property;
+use syn::{parse::ParseStream, Lit};
+use tagged_union::{ChildTaggedUnion, TaggedUnion};
+use type_name::TypeName;
+
+mod array;
+mod array_of_tables;
+mod constraints;
+mod derive;
+mod docs;
+mod document;
+mod enumeration;
+mod inline_table;
+mod item;
+mod json_schema;
+mod map_table;
+mod property;
+mod sample;
+mod struct_impls;
+mod table;
+mod table_attributes;
+mod tagged_union;
+mod toml_struct;
+mod type_name;
+
+#[proc_macro_derive(TomlSchema, attributes(toml))]
+pub fn derive_toml_schema(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as derive::DeriveInput);
+
+    quote::quote! {
+        #input
+    }
+    .into()
+}
+
+#[proc_macro]
+pub fn toml(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as TomlDocument);
+
+    quote::quote! {
+        #input
+    }
+    .into()
+}
+
+#[derive(Clone)]
+struct TableContents(Vec<Property>);
+
+impl syn::parse::Parse for TableContents {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut properties = Vec::new();
+        loop {
+            // Attributes followed by a heading belong to the next table
+            let fork = input.fork();
+            fork.call(syn::Attribute::parse_outer)?;
+            if !fork.peek(syn::Ident) {
+                break;
+            }
+            properties.push(input.parse()?);
+            input.parse::<syn::Token![;]>()?;
+        }
+
+        Ok(TableContents(properties))
+    }
+}
+
+impl TryInto<Value> for syn::Lit {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<Value, Self::Error> {
+        Ok(match self {
+            Lit::Bool(value) => value.value.into(),
+            Lit::Str(value) => value.into(),
+            Lit::Int(value) => match value.suffix() {
+                "f32" | "f64" => syn::LitFloat::new(
+                    &format!("{}.0{}", value.base10_digits(), value.suffix()),
+                    value.span(),
+                )
+                .into(),
+                suffix => {
+                    check_integer(value.base10_digits(), suffix)?;
+                    value.into()
+                }
+            },
+            Lit::Float(value) => match value.suffix() {
+                "" | "f32" | "f64" => value.into(),
+                suffix => anyhow::bail!("Invalid suffix `{suffix}` on a TOML float"),
+            },
+            _ => anyhow::bail!("Invalid literal used as TOML value"),
+        })
+    }
+}
+
+/// Checks that the digits of an integer literal fit in the integer type named by its suffix, or in `i64` when
+/// it has none.
+pub(crate) fn check_integer(digits: &str, suffix: &str) -> anyhow::Result<()> {
+    macro_rules! check {
+        ($($integer: ident),*) => {
+            match suffix {
+                "" => fits::<i64>(digits, "i64")?,
+                $(
+                    stringify!($integer) => fits::<$integer>(digits, suffix)?,
+                )*
+                _ => anyhow::bail!("Invalid suffix `{suffix}` on a TOML integer"),
+            }
+        };
+    }
+
+    check!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    Ok(())
+}
+
+/// The name of the enum variant generated for a string, which is the string in Pascal case. This is used for the
+/// values of enumerations and the tags of tagged unions.
+fn variant_name(value: &syn::LitStr) -> syn::Result<syn::Ident> {
+    let name = value.value().to_case(convert_case::Case::Pascal);
+    syn::parse_str::<syn::Ident>(&name)
+        .map(|variant| syn::Ident::new(&variant.to_string(), value.span()))
+        .map_err(|_error| {
+            syn::Error::new(
+                value.span(),
+                format!(
+                    "`{}` cannot be used as the name of an enum variant",
+                    value.value()
+                ),
+            )
+        })
+}
+
+fn fits<T: std::str::FromStr>(digits: &str, type_name: &str) -> anyhow::Result<()> {
+    if digits.parse::<T>().is_ok() {
+        return Ok(());
+    }
+    if digits.starts_with('-') && type_name.starts_with('u') {
+        anyhow::bail!("`{type_name}` cannot be negative");
+    }
+    anyhow::bail!("`{digits}` does not fit in `{type_name}`")
+}
+
+#[derive(try_as::macros::From, Clone)]
+enum Value {
+    Boolean(bool),
+    Integer(syn::LitInt),
+    Float(syn::LitFloat),
+    String(syn::LitStr),
+    Array(Array),
+    InlineTable(InlineTable),
+    Enum(Enumeration),
+    Union(TaggedUnion),
+}
+
+impl Value {
+    /// The Rust type of a field holding this value. Inline tables, enums and tagged unions are named after their
+    /// parent, so they are handled by the property that holds them instead.
+    fn rust_type(&self) -> proc_macro2::TokenStream {
+        match self {
+            Value::Boolean(_) => quote::quote! { bool },
+            Value::String(_) => quote::quote! { String },
+            Value::Integer(value) => match value.suffix() {
+                "" => quote::quote! { i64 },
+                suffix => {
+                    let suffix = quote::format_ident!("{suffix}");
+                    quote::quote! { #suffix }
+                }
+            },
+            Value::Float(value) => match value.suffix() {
+                "" => quote::quote! { f64 },
+                suffix => {
+                    let suffix = quote::format_ident!("{suffix}");
+                    quote::quote! { #suffix }
+                }
+            },
+            Value::Array(array) => {
+                let element_type = &array.element_type;
+                quote::quote! { ::std::vec::Vec<#element_type> }
+            }
+            Value::InlineTable(_) => unreachable!("inline tables are typed by their property"),
+            Value::Enum(_) | Value::Union(_) => unreachable!("enums are typed by their property"),
+        }
+    }
+
+    /// Converts this value to an explicitly given type. Values for types that aren't built into TOML, like
+    /// `std::path::PathBuf`, are converted with `Into` by the generated code instead.
+    fn conform_to_type(
+        self,
+        explicit_type: &syn::Type,
+        span: proc_macro2::Span,
+    ) -> syn::Result<Value> {
+        let mismatch = || {
+            syn::Error::new(
+                span,
+                format!(
+                    "Expected a value of type `{}`",
+                    quote::quote! { #explicit_type }
+                ),
+            )
+        };
+
+        if let Some(inner_type) = option_inner_type(explicit_type) {
+            return self.conform_to_type(inner_type, span);
+        }
+
+        if let Some(element_type) = vec_element_type(explicit_type) {
+            let Value::Array(array) = self else {
+                return Err(mismatch());
+            };
+            let elements = array
+                .elements
+                .into_iter()
+                .map(|element| element.conform_to_type(element_type, span))
+                .collect::<syn::Result<Vec<_>>>()?;
+            return Ok(Value::Array(Array {
+                elements,
+                element_type: Box::new(element_type.clone()),
+            }));
+        }
+
+        let name = type_name(explicit_type);
+        match (self, name.as_deref()) {
+            (Value::InlineTable(_), _) => Err(syn::Error::new(
+                span,
+                "Inline tables cannot have an explicit type",
+            )),
+            (Value::Enum(_) | Value::Union(_), _) => {
+                Err(syn::Error::new(span, "Enums cannot have an explicit type"))
+            }
+            (Value::Integer(value), Some(name)) if INTEGER_TYPES.contains(&name) => {
+                if !value.suffix().is_empty() && value.suffix() != name {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!("Expected a value of type `{name}`"),
+                    ));
+                }
+                check_integer(value.base10_digits(), name)
+                    .map_err(|error| syn::Error::new(value.span(), error))?;
+                Ok(Value::Integer(value))
+            }
+            (Value::Integer(value), Some("f32" | "f64")) if value.suffix().is_empty() => {
+                Ok(Value::Float(syn::LitFloat::new(
+                    &format!("{}.0", value.base10_digits()),
+                    value.span(),
+                )))
+            }
+            (Value::Float(value), Some(name @ ("f32" | "f64")))
+                if value.suffix().is_empty() || value.suffix() == name =>
+            {
+                Ok(Value::Float(value))
+            }
+            (value @ Value::Boolean(_), Some("bool")) => Ok(value),
+            (value @ Value::String(_), Some("String")) => Ok(value),
+            (_, Some(name))
+                if INTEGER_TYPES.contains(&name)
+                    || matches!(name, "f32" | "f64" | "bool" | "String") =>
+            {
+                Err(mismatch())
+            }
+            (value, _) => Ok(value),
+        }
+    }
+
+    /// Converts this value to the type of `expected`, such as when it overrides a default declared elsewhere
+    /// in the schema.
+    fn conform(self, expected: &Value, span: proc_macro2::Span) -> syn::Result<Value> {
+        match (self, expected) {
+            (Value::InlineTable(_) | Value::Union(_), _)
+            | (_, Value::InlineTable(_) | Value::Union(_)) => Err(syn::Error::new(
+                span,
+                "Inline tables cannot be overridden here",
+            )),
+            (Value::String(value), Value::Enum(expected)) => {
+                Ok(Value::Enum(expected.select(&value)?))
+            }
+            (_, Value::Enum(expected)) => Err(syn::Error::new(span, expected.expected())),
+            (Value::Enum(_), _) => Err(syn::Error::new(span, "Enums cannot be overridden here")),
+            (Value::Integer(value), Value::Integer(expected)) if value.suffix().is_empty() => {
+                check_integer(value.base10_digits(), expected.suffix())
+                    .map_err(|error| syn::Error::new(value.span(), error))?;
+                Ok(Value::Integer(value))
+            }
+            (Value::Integer(value), Value::Float(_)) if value.suffix().is_empty() => {
+                Ok(Value::Float(syn::LitFloat::new(
+                    &format!("{}.0", value.base10_digits()),
+                    value.span(),
+                )))
+            }
+            (Value::Float(value), Value::Float(_)) if value.suffix().is_empty() => {
+                Ok(Value::Float(value))
+            }
+            (value, expected) => {
+                let (found, expected) = (value.rust_type(), expected.rust_type());
+                if found.to_string() != expected.to_string() {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("Expected a value of type `{expected}`, found `{found}`"),
+                    ));
+                }
+                Ok(value)
+            }
+        }
+    }
+}
+
+const INTEGER_TYPES: [&str; 10] = [
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+];
+
+/// The name of a type written as a plain path without generics, like `u16` or `std::path::PathBuf`.
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            let segment = path.segments.last()?;
+            segment
+                .arguments
+                .is_none()
+                .then(|| segment.ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The element type of a `Vec<T>`.
+fn vec_element_type(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_argument(ty, "Vec")
+}
+
+/// The inner type of an `Option<T>`.
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_argument(ty, "Option")
+}
+
+/// The type argument of a type with a single one, like `Vec<T>`, if the type has the given name.
+fn generic_argument<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first()? {
+                syn::GenericArgument::Type(argument) => Some(argument),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether a field of the given type is returned by value from its getter.
+fn is_copy_type(ty: &syn::Type) -> bool {
+    match type_name(ty).as_deref() {
+        Some(name) => {
+            INTEGER_TYPES.contains(&name) || matches!(name, "bool" | "char" | "f32" | "f64")
+        }
+        None => false,
+    }
+}
+
+/// The return type of the getter for a field of the given type, along with the expression that reads the field
+/// through `field`.
+fn getter_type(
+    field_type: &syn::Type,
+    field: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if let Some(inner_type) = option_inner_type(field_type) {
+        if is_copy_type(inner_type) {
+            return (quote::quote! { #field_type }, field);
+        }
+        let (return_type, _) = getter_type(inner_type, field.clone());
+        let method = if vec_element_type(inner_type).is_some()
+            || matches!(type_name(inner_type).as_deref(), Some("String" | "PathBuf"))
+        {
+            quote::quote! { as_deref }
+        } else {
+            quote::quote! { as_ref }
+        };
+        return (
+            quote::quote! { ::core::option::Option<#return_type> },
+            quote::quote! { #field.#method() },
+        );
+    }
+
+    if let Some(element_type) = vec_element_type(field_type) {
+        return (
+            quote::quote! { &[#element_type] },
+            quote::quote! { &#field },
+        );
+    }
+
+    if is_copy_type(field_type) {
+        return (quote::quote! { #field_type }, field);
+    }
+
+    match type_name(field_type).as_deref() {
+        Some("String") => (quote::quote! { &str }, quote::quote! { &#field }),
+        Some("PathBuf") => (
+            quote::quote! { &::std::path::Path },
+            quote::quote! { &#field },
+        ),
+        _ => (quote::quote! { &#field_type }, quote::quote! { &#field }),
+    }
+}
+
+struct ChildValue {
+    value: Value,
+    parent_name: TypeName,
+    property_name: syn::Ident,
+}
+
+impl quote::ToTokens for ChildValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match &self.value {
+            Value::Boolean(value) => tokens.extend(quote::quote! { #value }),
+            Value::String(value) => {
+                tokens.extend(quote::quote_spanned! { value.span()=> #value.into() })
+            }
+            Value::Integer(value) => tokens.extend(quote::quote! { #value }),
+            Value::Float(value) => tokens.extend(quote::quote! { #value }),
+            Value::Array(array) => {
+                let elements = array.elements.iter().map(|element| ChildValue {
+                    value: element.clone(),
+                    parent_name: self.parent_name.clone(),
+                    property_name: self.property_name.clone(),
+                });
+                tokens.extend(quote::quote! { ::std::vec![#(#elements),*] })
+            }
+            Value::InlineTable(value) => {
+                let default =
+                    ChildInlineTableDefault(value.clone().into_child_inline_table(
+                        self.property_name.clone(),
+                        self.parent_name.clone(),
+                    ));
+                tokens.extend(quote::quote! { #default })
+            }
+            Value::Enum(value) => {
+                let name = self.parent_name.child(&self.property_name);
+                let variant = &value.variants()[value.selected];
+                tokens.extend(quote::quote! { #name::#variant })
+            }
+            Value::Union(union) => {
+                let default = ChildTaggedUnion {
+                    union: union.clone(),
+                    name: self.parent_name.child(&self.property_name),
+                }
+                .default_value();
+                tokens.extend(default)
+            }
+        }
+    }
+}
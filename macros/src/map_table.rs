@@ -0,0 +1,249 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    docs::Docs,
+    document::insert_item,
+    item::{ChildItem, Item},
+    table_attributes::TableAttributes,
+    toml_struct::TomlStruct,
+    type_name::TypeName,
+    TableContents,
+};
+
+/// A table whose keys are chosen by the user, declared as `[heading.*]`. Its contents declare the schema of every
+/// entry.
+#[derive(Clone)]
+pub(crate) struct MapTable {
+    heading: syn::Ident,
+    items: Vec<Item>,
+
+    /// The super-tables named before the heading, such as `a` in `[a.servers.*]`. These are resolved, and this
+    /// emptied, when the map is inserted into the document.
+    pub supertables: Vec<syn::Ident>,
+
+    pub attributes: TableAttributes,
+}
+
+impl syn::parse::Parse for MapTable {
+    fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+        let heading;
+        syn::bracketed!(heading in input);
+        let mut supertables = vec![heading.parse::<syn::Ident>()?];
+        while heading.peek(syn::Token![.]) && heading.peek2(syn::Ident) {
+            heading.parse::<syn::Token![.]>()?;
+            supertables.push(heading.parse()?);
+        }
+        heading.parse::<syn::Token![.]>()?;
+        heading.parse::<syn::Token![*]>()?;
+        if !heading.is_empty() {
+            return Err(heading.error("Expected the end of the heading"));
+        }
+        let heading = supertables.pop().unwrap();
+
+        let contents: TableContents = input.parse()?;
+        let mut items = Vec::new();
+        for property in contents.0 {
+            insert_item(&mut items, Item::from(property))?;
+        }
+
+        Ok(MapTable {
+            heading,
+            items,
+            supertables,
+            attributes: TableAttributes::default(),
+        })
+    }
+}
+
+/// Whether the heading in the brackets of the given input ends with `.*`, declaring a map table.
+pub(crate) fn is_map_heading(input: syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    let heading = (|| {
+        let heading;
+        syn::bracketed!(heading in fork);
+        heading.parse::<proc_macro2::TokenStream>()
+    })();
+    let Ok(heading) = heading else {
+        return false;
+    };
+    matches!(
+        heading.into_iter().last(),
+        Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == '*'
+    )
+}
+
+impl MapTable {
+    pub fn heading(&self) -> &syn::Ident {
+        &self.heading
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
+    }
+
+    pub fn into_child_map_table(self, parent_name: TypeName) -> ChildMapTable {
+        ChildMapTable {
+            map: self,
+            parent_name,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildMapTable {
+    map: MapTable,
+    parent_name: TypeName,
+}
+
+impl ChildMapTable {
+    pub fn name(&self) -> &syn::Ident {
+        &self.map.heading
+    }
+
+    pub fn docs(&self) -> &Docs {
+        &self.map.attributes.docs
+    }
+
+    /// The name of the struct generated for the entries of this map, such as `ConfigServersEntry`.
+    pub fn type_name(&self) -> TypeName {
+        self.parent_name.child(&self.map.heading).suffixed("Entry")
+    }
+
+    /// The items of the entries of this map, as items of the struct generated for them.
+    pub fn items(&self) -> Vec<ChildItem> {
+        let entry_type = self.type_name();
+        self.map
+            .items
+            .iter()
+            .map(|item| item.clone().into_child_item(entry_type.clone()))
+            .collect()
+    }
+}
+
+impl quote::ToTokens for ChildMapTable {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let entry_type = self.type_name();
+        let heading = &self.map.heading;
+        tokens.extend(quote::quote! {
+            #heading: ::std::collections::BTreeMap<String, #entry_type>
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining the struct for the
+/// entries of this map.
+#[derive(Clone)]
+pub struct ChildMapTablePrelude(pub ChildMapTable);
+
+impl std::ops::Deref for ChildMapTablePrelude {
+    type Target = ChildMapTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildMapTablePrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let items = self.items();
+
+        let name = self.type_name();
+        let prelude = items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_prelude())
+            .collect::<Vec<_>>();
+        let prelude = name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        let toml_struct = TomlStruct {
+            name,
+            items,
+            attributes: self.map.attributes.clone(),
+        };
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #toml_struct
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildMapTableDefault(pub ChildMapTable);
+
+impl std::ops::Deref for ChildMapTableDefault {
+    type Target = ChildMapTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildMapTableDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.map.heading.clone();
+
+        tokens.extend(quote::quote! {
+            #heading: ::std::collections::BTreeMap::new()
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the field initializer that
+/// reads this map out of a parsed TOML table, falling back to an empty map.
+#[derive(Clone)]
+pub struct ChildMapTableFromToml(pub ChildMapTable);
+
+impl std::ops::Deref for ChildMapTableFromToml {
+    type Target = ChildMapTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildMapTableFromToml {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.map.heading.clone();
+        let key = heading.unraw().to_string();
+
+        tokens.extend(quote::quote! {
+            #heading: ::toml_schema::__private::get_table(table, #key, path)?
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the getter method of this
+/// map.
+#[derive(Clone)]
+pub struct ChildMapTableGetter(pub ChildMapTable);
+
+impl std::ops::Deref for ChildMapTableGetter {
+    type Target = ChildMapTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildMapTableGetter {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.map.heading.clone();
+        let entry_type = self.type_name();
+
+        tokens.extend(quote::quote! {
+            pub fn #heading(&self) -> &::std::collections::BTreeMap<String, #entry_type> {
+                &self.#heading
+            }
+        });
+    }
+}
@@ -0,0 +1,583 @@
+use syn::{ext::IdentExt as _, spanned::Spanned as _};
+
+use crate::{
+    array::Array,
+    constraints::{Constraints, ValueType},
+    docs::Docs,
+    enumeration::{Enumeration, EnumerationPrelude},
+    inline_table::ChildInlineTablePrelude,
+    option_inner_type,
+    table_attributes::TableAttributes,
+    tagged_union::{ChildTaggedUnion, ChildTaggedUnionPrelude, TaggedUnion},
+    type_name::TypeName,
+    vec_element_type, ChildValue, InlineTable, Value,
+};
+
+#[derive(Clone)]
+pub(crate) struct Property {
+    pub name: syn::Ident,
+
+    /// The default value, which is `None` for a key declared with only a type, like `name: String;`. Such keys
+    /// are required, unless they are optional.
+    pub value: Option<Value>,
+
+    /// The type given after the name, like `port: u16 = 8080;`. Without one, the type is inferred from the value.
+    /// Optional keys like `proxy?: String;` are given the type `Option<String>`.
+    pub explicit_type: Option<Box<syn::Type>>,
+
+    /// The constraints given as attributes, like `#[range(1..=65535)]`.
+    pub constraints: Constraints,
+
+    /// The doc comments given with `///`.
+    pub docs: Docs,
+}
+
+impl syn::parse::Parse for Property {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut attributes = input.call(syn::Attribute::parse_outer)?;
+        let docs = Docs::take(&mut attributes)?;
+        let mut segments = vec![input.parse::<syn::Ident>()?];
+        while input.peek(syn::Token![.]) {
+            input.parse::<syn::Token![.]>()?;
+            segments.push(input.parse()?);
+        }
+        let name = segments.pop().unwrap();
+
+        // A dotted key like `a.b = 1` is a property `a` holding a table with the property `b`
+        let mut property = Property::parse_value(name, input)?;
+        property.constraints = Constraints::from_attributes(attributes)?;
+        property.docs = docs;
+        property.check_constraints()?;
+        Ok(segments
+            .into_iter()
+            .rev()
+            .fold(property, |property, supertable| Property {
+                name: supertable,
+                value: Some(Value::InlineTable(InlineTable {
+                    properties: vec![property],
+                    dotted: true,
+                    attributes: TableAttributes::default(),
+                })),
+                explicit_type: None,
+                constraints: Constraints::default(),
+                docs: Docs::default(),
+            }))
+    }
+}
+
+impl Property {
+    /// Gives the types generated for the value of this property the derives, other attributes and visibilities of
+    /// the enclosing table.
+    pub fn inherit_attributes(&mut self, parent: &TableAttributes) {
+        match &mut self.value {
+            Some(Value::InlineTable(table)) => {
+                table.attributes.inherit(parent);
+                for property in &mut table.properties {
+                    property.inherit_attributes(parent);
+                }
+            }
+            Some(Value::Enum(enumeration)) => enumeration.attributes.inherit(parent),
+            Some(Value::Union(union)) => {
+                union.attributes.inherit(parent);
+                for alternative in &mut union.alternatives {
+                    for property in &mut alternative.properties {
+                        property.inherit_attributes(parent);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn parse_value(name: syn::Ident, input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Optional key
+        if input.peek(syn::Token![?]) {
+            input.parse::<syn::Token![?]>()?;
+            input.parse::<syn::Token![:]>()?;
+            let inner_type: syn::Type = input.parse()?;
+            let explicit_type: syn::Type = syn::parse_quote! { Option<#inner_type> };
+            return Property::parse_optional(name, explicit_type, input);
+        }
+
+        // Explicit type
+        let explicit_type = if input.peek(syn::Token![:]) {
+            input.parse::<syn::Token![:]>()?;
+            let explicit_type: syn::Type = input.parse()?;
+            if option_inner_type(&explicit_type).is_some() {
+                return Property::parse_optional(name, explicit_type, input);
+            }
+            if !input.peek(syn::Token![=]) {
+                return Ok(Property {
+                    name,
+                    value: None,
+                    explicit_type: Some(Box::new(explicit_type)),
+                    constraints: Constraints::default(),
+                    docs: Docs::default(),
+                });
+            }
+            Some(explicit_type)
+        } else {
+            None
+        };
+        input.parse::<syn::Token![=]>()?;
+
+        // Inline table
+        if input.peek(syn::token::Brace) {
+            if let Some(explicit_type) = explicit_type {
+                return Err(syn::Error::new_spanned(
+                    explicit_type,
+                    "Inline tables cannot have an explicit type",
+                ));
+            }
+            let contents;
+            let brace = syn::braced!(contents in input);
+            let contents: InlineTable = contents.parse()?;
+
+            // Tagged union
+            if input.peek(syn::Token![|]) {
+                let mut alternatives = vec![(contents, brace.span.join())];
+                while input.peek(syn::Token![|]) {
+                    input.parse::<syn::Token![|]>()?;
+                    let contents;
+                    let brace = syn::braced!(contents in input);
+                    alternatives.push((contents.parse()?, brace.span.join()));
+                }
+                return Ok(Property {
+                    name,
+                    value: Some(Value::Union(TaggedUnion::new(alternatives)?)),
+                    explicit_type: None,
+                    constraints: Constraints::default(),
+                    docs: Docs::default(),
+                });
+            }
+
+            return Ok(Property {
+                name,
+                value: Some(Value::InlineTable(contents)),
+                explicit_type: None,
+                constraints: Constraints::default(),
+                docs: Docs::default(),
+            });
+        }
+
+        // Enum
+        let expr = input.parse::<syn::Expr>()?;
+        if let syn::Expr::Binary(syn::ExprBinary {
+            op: syn::BinOp::BitOr(_),
+            ..
+        }) = expr
+        {
+            if let Some(explicit_type) = explicit_type {
+                return Err(syn::Error::new_spanned(
+                    explicit_type,
+                    "Enums cannot have an explicit type",
+                ));
+            }
+            return Ok(Property {
+                name,
+                value: Some(Value::Enum(Enumeration::new(expr)?)),
+                explicit_type: None,
+                constraints: Constraints::default(),
+                docs: Docs::default(),
+            });
+        }
+
+        // Literal value
+        let value = match &explicit_type {
+            Some(explicit_type) => typed_expr_value(expr, explicit_type)?,
+            None => expr_value(expr)?,
+        };
+
+        Ok(Property {
+            name,
+            value: Some(value),
+            explicit_type: explicit_type.map(Box::new),
+            constraints: Constraints::default(),
+            docs: Docs::default(),
+        })
+    }
+
+    fn parse_optional(
+        name: syn::Ident,
+        explicit_type: syn::Type,
+        input: syn::parse::ParseStream,
+    ) -> syn::Result<Self> {
+        if input.peek(syn::Token![=]) {
+            return Err(
+                input.error("Optional keys default to `None` and cannot have a default value")
+            );
+        }
+        Ok(Property {
+            name,
+            value: None,
+            explicit_type: Some(Box::new(explicit_type)),
+            constraints: Constraints::default(),
+            docs: Docs::default(),
+        })
+    }
+
+    /// Whether this is an optional key, which holds an `Option` and defaults to `None`.
+    pub fn is_optional(&self) -> bool {
+        self.explicit_type
+            .as_deref()
+            .is_some_and(|explicit_type| option_inner_type(explicit_type).is_some())
+    }
+
+    /// Whether this property can be left out, because it and every property of it has a default value.
+    pub fn has_default(&self) -> bool {
+        if self.is_optional() {
+            return true;
+        }
+        match &self.value {
+            Some(Value::InlineTable(table)) => table.properties.iter().all(Property::has_default),
+            Some(Value::Union(union)) => union.has_default(),
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Checks a value that overrides the default of this property, converting it to the type of this property.
+    pub fn check_override(&self, value: Value, span: proc_macro2::Span) -> syn::Result<Value> {
+        let value = match (&self.explicit_type, &self.value) {
+            (Some(explicit_type), _) => value.conform_to_type(explicit_type, span),
+            (None, Some(expected)) => value.conform(expected, span),
+            (None, None) => unreachable!("properties have either a type or a value"),
+        }?;
+        self.constraints.check(&value, span)?;
+        Ok(value)
+    }
+
+    /// Checks that the constraints of this property apply to its type, and that its default satisfies them.
+    fn check_constraints(&self) -> syn::Result<()> {
+        self.constraints.check_applicable(&self.value_type())?;
+        if let Some(value) = &self.value {
+            self.constraints.check(value, self.name.span())?;
+        }
+        Ok(())
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        let explicit_type = self.explicit_type.as_deref().map(|explicit_type| {
+            crate::type_name(option_inner_type(explicit_type).unwrap_or(explicit_type))
+        });
+        let integer_type = match (&explicit_type, &self.value) {
+            (Some(Some(name)), _) => crate::INTEGER_TYPES
+                .contains(&name.as_str())
+                .then(|| name.clone()),
+            (None, Some(Value::Integer(value))) => Some(match value.suffix() {
+                "" => "i64".to_owned(),
+                suffix => suffix.to_owned(),
+            }),
+            _ => None,
+        };
+        let (number, float, string) = match (explicit_type, &self.value) {
+            (Some(Some(name)), _) => (
+                crate::INTEGER_TYPES.contains(&name.as_str())
+                    || matches!(name.as_str(), "f32" | "f64"),
+                matches!(name.as_str(), "f32" | "f64"),
+                name == "String",
+            ),
+            (Some(None), _) => (false, false, false),
+            (None, Some(Value::Integer(_))) => (true, false, false),
+            (None, Some(Value::Float(_))) => (true, true, false),
+            (None, Some(Value::String(_))) => (false, false, true),
+            (None, _) => (false, false, false),
+        };
+        ValueType {
+            number,
+            float,
+            string,
+            optional: self.is_optional(),
+            integer_type,
+        }
+    }
+
+    pub fn into_child_property(self, parent_name: TypeName) -> ChildProperty {
+        ChildProperty {
+            property: self,
+            parent_name,
+        }
+    }
+}
+
+/// Converts the expression on the right-hand side of a property with an explicit type into its value, checking
+/// it against that type.
+pub(crate) fn typed_expr_value(expr: syn::Expr, explicit_type: &syn::Type) -> syn::Result<Value> {
+    // Arrays are built with the element type up front, so that empty arrays need no type of their own
+    if let (syn::Expr::Array(array), Some(element_type)) = (&expr, vec_element_type(explicit_type))
+    {
+        return Ok(Value::Array(Array::new(
+            array.clone(),
+            Some(element_type.clone()),
+        )?));
+    }
+
+    let span = expr.span();
+    expr_value(expr)?.conform_to_type(explicit_type, span)
+}
+
+/// Converts the expression on the right-hand side of a property into its value.
+pub(crate) fn expr_value(expr: syn::Expr) -> syn::Result<Value> {
+    match expr {
+        syn::Expr::Lit(literal) => literal_value(literal.lit),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match *expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(value),
+                ..
+            }) => literal_value(syn::Lit::Int(syn::LitInt::new(
+                &format!("-{value}"),
+                value.span(),
+            ))),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Float(value),
+                ..
+            }) => literal_value(syn::Lit::Float(syn::LitFloat::new(
+                &format!("-{value}"),
+                value.span(),
+            ))),
+            expr => Err(syn::Error::new_spanned(
+                expr,
+                "Invalid value for a TOML value",
+            )),
+        },
+        syn::Expr::Array(array) => Ok(Value::Array(Array::new(array, None)?)),
+        syn::Expr::Cast(syn::ExprCast { expr, ty, .. }) => match (*expr, *ty) {
+            (syn::Expr::Array(array), syn::Type::Slice(element_type)) => {
+                Ok(Value::Array(Array::new(array, Some(*element_type.elem))?))
+            }
+            (expr, _) => Err(syn::Error::new_spanned(
+                expr,
+                "Only arrays can be given an explicit type with `as [T]`",
+            )),
+        },
+        expr => Err(syn::Error::new_spanned(
+            expr,
+            "Invalid value for a TOML value",
+        )),
+    }
+}
+
+fn literal_value(literal: syn::Lit) -> syn::Result<Value> {
+    let span = literal.span();
+    literal
+        .try_into()
+        .map_err(|error: anyhow::Error| syn::Error::new(span, error))
+}
+
+#[derive(Clone)]
+pub struct ChildProperty {
+    pub parent_name: TypeName,
+    pub property: Property,
+}
+
+impl ChildProperty {
+    pub fn field_type(&self) -> proc_macro2::TokenStream {
+        if let Some(explicit_type) = &self.property.explicit_type {
+            return quote::quote! { #explicit_type };
+        }
+
+        match &self.property.value {
+            Some(Value::InlineTable(_) | Value::Enum(_) | Value::Union(_)) => {
+                let type_name = self.parent_name.child(&self.property.name);
+                quote::quote! { #type_name }
+            }
+            Some(value) => value.rust_type(),
+            None => unreachable!("properties have either a type or a value"),
+        }
+    }
+
+    pub fn default_value(&self) -> proc_macro2::TokenStream {
+        match &self.property.value {
+            Some(value) => {
+                let value = ChildValue {
+                    value: value.clone(),
+                    parent_name: self.parent_name.clone(),
+                    property_name: self.property.name.clone(),
+                };
+                if self.property.is_optional() {
+                    quote::quote! { ::core::option::Option::Some(#value) }
+                } else {
+                    quote::quote! { #value }
+                }
+            }
+            None if self.property.is_optional() => quote::quote! { ::core::option::Option::None },
+            None => unreachable!("required properties have no default"),
+        }
+    }
+}
+
+impl ChildProperty {
+    /// The statements that check the constraints of this property against the value in the variable `value`,
+    /// reporting errors for the key at `path`.
+    pub fn runtime_checks(&self, path: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        self.property
+            .constraints
+            .runtime_checks(&self.property.value_type(), path)
+    }
+
+    /// The statements that check the integer in the expression `integer`, an `Option<i128>`, against the range
+    /// of this property before it is converted to the type of the property.
+    pub fn integer_checks(
+        &self,
+        integer: proc_macro2::TokenStream,
+        path: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        self.property
+            .constraints
+            .integer_checks(&self.property.value_type(), integer, path)
+    }
+}
+
+impl quote::ToTokens for ChildProperty {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = &self.property.name;
+        let field_type = self.field_type();
+        tokens.extend(quote::quote! { #name: #field_type });
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildPropertyPrelude(pub ChildProperty);
+
+impl quote::ToTokens for ChildPropertyPrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self.0.property.value.clone() {
+            Some(Value::InlineTable(inline_table)) => {
+                ChildInlineTablePrelude(inline_table.into_child_inline_table(
+                    self.0.property.name.clone(),
+                    self.0.parent_name.clone(),
+                ))
+                .to_tokens(tokens)
+            }
+            Some(Value::Enum(enumeration)) => EnumerationPrelude {
+                enumeration,
+                name: self.0.parent_name.child(&self.0.property.name),
+            }
+            .to_tokens(tokens),
+            Some(Value::Union(union)) => ChildTaggedUnionPrelude(ChildTaggedUnion {
+                union,
+                name: self.0.parent_name.child(&self.0.property.name),
+            })
+            .to_tokens(tokens),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildPropertyDefault(pub ChildProperty);
+
+impl std::ops::Deref for ChildPropertyDefault {
+    type Target = ChildProperty;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildPropertyDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = self.property.name.clone();
+        let value = self.default_value();
+        tokens.extend(quote::quote! {
+            #name: #value
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the field initializer that
+/// reads this property out of a parsed TOML table, falling back to its default.
+#[derive(Clone)]
+pub struct ChildPropertyFromToml(pub ChildProperty);
+
+impl std::ops::Deref for ChildPropertyFromToml {
+    type Target = ChildProperty;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildPropertyFromToml {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = self.property.name.clone();
+        let key = name.unraw().to_string();
+        let path = quote::quote! { &::toml_schema::__private::join(path, #key) };
+        let integer_checks = self.integer_checks(
+            quote::quote! { ::toml_schema::__private::integer(table.get(#key)) },
+            path.clone(),
+        );
+        let checks = self.runtime_checks(path);
+        let get = quote::quote! {
+            ::toml_schema::__private::get(table, #key, path)?
+        };
+        let value = match &self.property.value {
+            // Inline tables are read from an empty table when missing, which fills in their defaults and reports
+            // any required keys in them
+            Some(Value::InlineTable(_)) => quote::quote! {
+                ::toml_schema::__private::get_table(table, #key, path)?
+            },
+            Some(_) if self.property.has_default() => {
+                let default = self.default_value();
+                quote::quote! {
+                    match #get {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => #default,
+                    }
+                }
+            }
+            None if self.property.is_optional() => get,
+            _ => quote::quote! {
+                ::toml_schema::__private::get_required(table, #key, path)?
+            },
+        };
+
+        tokens.extend(if checks.is_empty() {
+            quote::quote! { #name: #value }
+        } else {
+            let field_type = self.field_type();
+            quote::quote! {
+                #name: {
+                    #integer_checks
+                    let value: #field_type = #value;
+                    #checks
+                    value
+                }
+            }
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the getter method of this
+/// property.
+#[derive(Clone)]
+pub struct ChildPropertyGetter(pub ChildProperty);
+
+impl std::ops::Deref for ChildPropertyGetter {
+    type Target = ChildProperty;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildPropertyGetter {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = self.property.name.clone();
+        let field_type = self.field_type();
+        let (return_type, value) = match &self.property.value {
+            // Enums are `Copy`, so they are returned by value
+            Some(Value::Enum(_)) => (field_type, quote::quote! { self.#name }),
+            _ => crate::getter_type(
+                &syn::parse_quote! { #field_type },
+                quote::quote! { self.#name },
+            ),
+        };
+        tokens.extend(quote::quote! {
+            pub fn #name(&self) -> #return_type {
+                #value
+            }
+        });
+    }
+}
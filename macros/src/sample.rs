@@ -0,0 +1,299 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    docs::Docs, item::Item, option_inner_type, property::Property, type_name, vec_element_type,
+    Value,
+};
+
+/// Renders the sample config returned by `sample_toml`, which lists every table and key of the schema with its
+/// default value, type and doc comments. Optional keys, and the example entries of arrays of tables and maps, are
+/// commented out.
+#[derive(Default)]
+pub(crate) struct Sample {
+    out: String,
+
+    /// Whether a header was just written, so that its keys follow it without an empty line.
+    after_header: bool,
+}
+
+impl Sample {
+    pub fn render(docs: &Docs, items: &[Item]) -> String {
+        let mut sample = Sample::default();
+        sample.comment(&docs.lines());
+        sample.table(items, "", false);
+        sample.out
+    }
+
+    fn table(&mut self, items: &[Item], path: &str, commented: bool) {
+        let (properties, sections): (Vec<_>, Vec<_>) = items
+            .iter()
+            .partition(|item| matches!(item, Item::Property(_)));
+        for item in properties {
+            if let Item::Property(property) = item {
+                self.property(property, commented);
+            }
+        }
+        for item in sections {
+            self.section(item, path, commented);
+        }
+    }
+
+    fn property(&mut self, property: &Property, commented: bool) {
+        self.start_block();
+        self.comment(&property.docs.lines());
+        self.comment(&[format!("Type: {}", describe_type(property))]);
+
+        let key = property.name.unraw();
+        let value = property_value(property);
+        self.line(
+            commented || property.is_optional() || value.is_none(),
+            &format!("{key} = {}", value.as_deref().unwrap_or("...")),
+        );
+    }
+
+    fn section(&mut self, item: &Item, path: &str, commented: bool) {
+        let path = join(path, &item.name().unraw().to_string());
+        match item {
+            Item::Table(table) => {
+                // Super-tables that only hold other tables don't need a heading of their own
+                let docs = &table.attributes.docs;
+                let has_properties = table
+                    .items()
+                    .iter()
+                    .any(|item| matches!(item, Item::Property(_)));
+                if has_properties || table.items().is_empty() || !docs.is_empty() {
+                    self.header(docs, commented, &format!("[{path}]"));
+                }
+                self.table(table.items(), &path, commented);
+            }
+            Item::ArrayOfTables(array) => {
+                // A first block that isn't an element of the default value is written as a commented out example
+                let example = !array.first_is_entry();
+                self.header(
+                    &array.attributes.docs,
+                    commented || example,
+                    &format!("[[{path}]]"),
+                );
+                self.table(array.items(), &path, commented || example);
+
+                for entry in array.entries() {
+                    self.header(&Docs::default(), commented, &format!("[[{path}]]"));
+                    self.entry("", &entry.0, commented);
+                }
+            }
+            Item::MapTable(map) => {
+                let path = format!("{path}.name");
+                self.header(&map.attributes.docs, true, &format!("[{path}]"));
+                self.table(map.items(), &path, true);
+            }
+            Item::Property(_) => unreachable!("properties are not sections"),
+        }
+    }
+
+    /// Writes the keys set by an element of the default value of an array of tables, with the keys of tables
+    /// created by dotted keys written as dotted keys again.
+    fn entry(&mut self, prefix: &str, properties: &[Property], commented: bool) {
+        for property in properties {
+            let key = format!("{prefix}{}", property.name.unraw());
+            match &property.value {
+                Some(Value::InlineTable(table)) if table.dotted => {
+                    self.entry(&format!("{key}."), &table.properties, commented)
+                }
+                _ => {
+                    let value = property_value(property).unwrap_or_default();
+                    self.line(commented, &format!("{key} = {value}"));
+                }
+            }
+        }
+    }
+
+    fn header(&mut self, docs: &Docs, commented: bool, header: &str) {
+        self.start_block();
+        self.comment(&docs.lines());
+        self.line(commented, header);
+        self.after_header = true;
+    }
+
+    /// Separates what follows from what came before with an empty line, unless it directly follows a header.
+    fn start_block(&mut self) {
+        if !self.out.is_empty() && !self.after_header {
+            self.out.push('\n');
+        }
+        self.after_header = false;
+    }
+
+    fn comment(&mut self, lines: &[String]) {
+        for line in lines {
+            if line.is_empty() {
+                self.out.push_str("#\n");
+            } else {
+                self.out.push_str(&format!("# {line}\n"));
+            }
+        }
+    }
+
+    fn line(&mut self, commented: bool, line: &str) {
+        if commented {
+            self.out.push_str("# ");
+        }
+        self.out.push_str(line);
+        self.out.push('\n');
+        self.after_header = false;
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// The type of a property as shown in its comment, like `u16` or `String, optional`.
+fn describe_type(property: &Property) -> String {
+    if let Some(explicit_type) = &property.explicit_type {
+        if let Some(inner_type) = option_inner_type(explicit_type) {
+            return format!("{}, optional", type_string(inner_type));
+        }
+        if property.value.is_none() {
+            return format!("{}, required", type_string(explicit_type));
+        }
+        return type_string(explicit_type);
+    }
+
+    match property
+        .value
+        .as_ref()
+        .expect("untyped keys have a default")
+    {
+        Value::Boolean(_) => "bool".to_owned(),
+        Value::Integer(value) => number_type(value.suffix(), "i64"),
+        Value::Float(value) => number_type(value.suffix(), "f64"),
+        Value::String(_) => "String".to_owned(),
+        Value::Array(array) => format!("Vec<{}>", type_string(&array.element_type)),
+        Value::Enum(enumeration) => format!("one of {}", quoted_list(&enumeration.values)),
+        Value::InlineTable(_) => "table".to_owned(),
+        Value::Union(union) => {
+            let tags = union
+                .alternatives
+                .iter()
+                .map(|alternative| alternative.tag_value.clone())
+                .collect::<Vec<_>>();
+            format!(
+                "table, with `{}` one of {}",
+                union.tag.unraw(),
+                quoted_list(&tags)
+            )
+        }
+    }
+}
+
+fn number_type(suffix: &str, default: &str) -> String {
+    if suffix.is_empty() {
+        default.to_owned()
+    } else {
+        suffix.to_owned()
+    }
+}
+
+fn quoted_list(values: &[syn::LitStr]) -> String {
+    values
+        .iter()
+        .map(|value| toml_string(&value.value()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn type_string(ty: &syn::Type) -> String {
+    quote::quote! { #ty }
+        .to_string()
+        .replace(' ', "")
+        .replace(',', ", ")
+}
+
+/// The value written for a property: its default, or a placeholder for its type if it has none. This is `None`
+/// for keys without a default whose type has no obvious placeholder.
+fn property_value(property: &Property) -> Option<String> {
+    match &property.value {
+        Some(value) => value_string(value),
+        None => {
+            let explicit_type = property.explicit_type.as_deref()?;
+            placeholder(option_inner_type(explicit_type).unwrap_or(explicit_type))
+        }
+    }
+}
+
+fn value_string(value: &Value) -> Option<String> {
+    Some(match value {
+        Value::Boolean(value) => value.to_string(),
+        Value::Integer(value) => value.base10_digits().to_owned(),
+        Value::Float(value) => {
+            let digits = value.base10_digits();
+            if digits.contains(['.', 'e', 'E']) {
+                digits.to_owned()
+            } else {
+                format!("{digits}.0")
+            }
+        }
+        Value::String(value) => toml_string(&value.value()),
+        Value::Array(array) => {
+            let elements = array
+                .elements
+                .iter()
+                .map(value_string)
+                .collect::<Option<Vec<_>>>()?;
+            format!("[{}]", elements.join(", "))
+        }
+        Value::Enum(enumeration) => toml_string(&enumeration.values[enumeration.selected].value()),
+        Value::InlineTable(table) => inline_table_string(None, &table.properties)?,
+        Value::Union(union) => {
+            let alternative = &union.alternatives[0];
+            let tag = format!(
+                "{} = {}",
+                union.tag.unraw(),
+                toml_string(&alternative.tag_value.value())
+            );
+            inline_table_string(Some(tag), &alternative.properties)?
+        }
+    })
+}
+
+/// An inline table with the values of the given properties, leaving out optional ones.
+fn inline_table_string(first: Option<String>, properties: &[Property]) -> Option<String> {
+    let entries = properties
+        .iter()
+        .filter(|property| !property.is_optional())
+        .map(|property| {
+            Some(format!(
+                "{} = {}",
+                property.name.unraw(),
+                property_value(property)?
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let entries = first.into_iter().chain(entries).collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Some("{}".to_owned());
+    }
+    Some(format!("{{ {} }}", entries.join(", ")))
+}
+
+/// A value for keys of the given type that don't have a default.
+pub(crate) fn placeholder(ty: &syn::Type) -> Option<String> {
+    if vec_element_type(ty).is_some() {
+        return Some("[]".to_owned());
+    }
+    let placeholder = match type_name(ty)?.as_str() {
+        "bool" => "false",
+        "String" | "PathBuf" => "\"\"",
+        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => "0",
+        "f32" | "f64" => "0.0",
+        _ => return None,
+    };
+    Some(placeholder.to_owned())
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_owned()).to_string()
+}
@@ -0,0 +1,217 @@
+use crate::table_attributes::TableAttributes;
+
+/// The runtime support shared by the structs generated by `toml!` and the structs deriving `TomlSchema`. The
+/// implementation for `quote::ToTokens` for this struct creates the tokens for the trait implementations that
+/// parse, set, validate and write the struct, along with its `try_set` method.
+pub(crate) struct StructImpls<'a> {
+    pub name: &'a syn::Ident,
+    pub fields: Vec<StructField>,
+    pub attributes: &'a TableAttributes,
+
+    /// Statements that run before the fields are read in `from_toml`, which their initializers can refer to.
+    pub before_from_toml: proc_macro2::TokenStream,
+}
+
+/// A field of a struct, as far as the shared runtime support needs to know about it.
+pub(crate) struct StructField {
+    pub name: syn::Ident,
+    pub key: String,
+    pub docs: Vec<String>,
+    pub field_type: proc_macro2::TokenStream,
+
+    /// Whether the field is written as one or more `[section]`s rather than as a key-value pair.
+    pub is_section: bool,
+
+    /// Whether keys can be set through the field, like `quiet` in `options.quiet`.
+    pub is_table: bool,
+
+    /// The initializer of the field in `from_toml`, like `name: value`, which reads it from the variable `table`.
+    pub from_toml: proc_macro2::TokenStream,
+
+    /// The expression that reads the field from the string in the variable `value` in `try_set`.
+    pub read: proc_macro2::TokenStream,
+
+    /// The statements that check the constraints of the field against the value in the variable `value`.
+    pub checks: proc_macro2::TokenStream,
+}
+
+impl quote::ToTokens for StructImpls<'_> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = self.name;
+        let before_from_toml = &self.before_from_toml;
+        let validators = &self.attributes.validators;
+        let comments = self.attributes.docs.lines();
+
+        let keys = self
+            .fields
+            .iter()
+            .map(|field| &field.key)
+            .collect::<Vec<_>>();
+        let field_names = self
+            .fields
+            .iter()
+            .map(|field| &field.name)
+            .collect::<Vec<_>>();
+        let from_toml = self.fields.iter().map(|field| &field.from_toml);
+
+        let (sections, properties): (Vec<_>, Vec<_>) =
+            self.fields.iter().partition(|field| field.is_section);
+        let property_names = properties.iter().map(|field| &field.name);
+        let property_keys = properties.iter().map(|field| &field.key);
+        let property_comments = properties.iter().map(|field| &field.docs);
+        let section_names = sections.iter().map(|field| &field.name);
+        let section_keys = sections.iter().map(|field| &field.key);
+        let section_comments = sections.iter().map(|field| &field.docs);
+
+        // Super-tables that only hold other tables don't need a heading of their own
+        let write_header = !properties.is_empty() || sections.is_empty();
+
+        let set_key = self.fields.iter().map(|field| {
+            let name = &field.name;
+            let key = &field.key;
+            let field_type = &field.field_type;
+            let read = &field.read;
+            let checks = &field.checks;
+            let nested = field.is_table.then(|| {
+                quote::quote! {
+                    (#key, ::core::option::Option::Some(rest)) => {
+                        let undo = ::toml_schema::__private::SetKey::set_key(&mut self.#name, rest, value, &field_path)?;
+                        ::std::boxed::Box::new(move |this: &mut Self| undo(&mut this.#name))
+                    }
+                }
+            });
+            quote::quote! {
+                (#key, ::core::option::Option::None) => {
+                    let value: #field_type = #read;
+                    #checks
+                    let previous = ::core::mem::replace(&mut self.#name, value);
+                    ::std::boxed::Box::new(move |this: &mut Self| this.#name = previous)
+                }
+                #nested
+            }
+        });
+
+        tokens.extend(quote::quote! {
+            impl ::toml_schema::__private::FromToml for #name {
+                fn from_toml(
+                    value: &::toml_schema::__private::Value,
+                    path: &str,
+                ) -> ::core::result::Result<Self, ::toml_schema::Error> {
+                    let table = ::toml_schema::__private::expect_table(value, path)?;
+                    ::toml_schema::__private::deny_unknown_keys(table, &[#(#keys),*], path)?;
+                    #before_from_toml
+                    let value = #name {
+                        #(
+                            #from_toml
+                        ),*
+                    };
+                    ::toml_schema::__private::Validate::validate(&value, path)?;
+                    ::core::result::Result::Ok(value)
+                }
+            }
+
+            impl ::toml_schema::__private::Validate for #name {
+                fn validate(&self, path: &str) -> ::core::result::Result<(), ::toml_schema::Error> {
+                    #(
+                        ::toml_schema::__private::run_validator(self, #validators, path)?;
+                    )*
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            impl ::core::str::FromStr for #name {
+                type Err = ::toml_schema::Error;
+
+                fn from_str(source: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let document = ::toml_schema::__private::parse_document(source)?;
+                    ::toml_schema::__private::FromToml::from_toml(&document, "")
+                }
+            }
+
+            impl ::toml_schema::__private::SetKey for #name {
+                fn set_key(
+                    &mut self,
+                    key: &str,
+                    value: &str,
+                    path: &str,
+                ) -> ::core::result::Result<::toml_schema::__private::Undo<Self>, ::toml_schema::Error> {
+                    let (head, rest) = ::toml_schema::__private::split_key(key);
+                    let field_path = ::toml_schema::__private::join(path, head);
+                    let undo: ::toml_schema::__private::Undo<Self> = match (head, rest) {
+                        #(
+                            #set_key
+                        )*
+                        _ => return ::core::result::Result::Err(::toml_schema::Error::UnknownKey {
+                            path: ::toml_schema::__private::join(path, key),
+                        }),
+                    };
+
+                    // Values that the validators reject are not kept
+                    if let ::core::result::Result::Err(error) = ::toml_schema::__private::Validate::validate(self, path) {
+                        undo(self);
+                        return ::core::result::Result::Err(error);
+                    }
+                    ::core::result::Result::Ok(undo)
+                }
+            }
+
+            impl ::toml_schema::__private::ToToml for #name {
+                fn write_inline(&self, out: &mut String) {
+                    ::toml_schema::__private::write_inline_table(out, &[
+                        #(
+                            (#keys, &self.#field_names as &dyn ::toml_schema::__private::ToToml)
+                        ),*
+                    ]);
+                }
+            }
+
+            impl ::toml_schema::__private::ToTomlTable for #name {
+                fn write_table(&self, out: &mut String, path: &str) {
+                    #(
+                        ::toml_schema::__private::write_key_value(
+                            out,
+                            #property_keys,
+                            &[#(#property_comments),*],
+                            &self.#property_names,
+                        );
+                    )*
+                    #(
+                        ::toml_schema::__private::write_section(
+                            out,
+                            path,
+                            #section_keys,
+                            &[#(#section_comments),*],
+                            &self.#section_names,
+                        );
+                    )*
+                }
+            }
+
+            impl ::toml_schema::__private::ToTomlSection for #name {
+                fn write_section(&self, out: &mut String, path: &str, docs: &[&str]) {
+                    if #write_header || !docs.is_empty() {
+                        ::toml_schema::__private::write_header(out, docs, &format!("[{path}]"));
+                    }
+                    ::toml_schema::__private::ToTomlTable::write_table(self, out, path);
+                }
+            }
+
+            impl ::core::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let mut out = String::new();
+                    ::toml_schema::__private::write_document(&mut out, &[#(#comments),*], self);
+                    f.write_str(&out)
+                }
+            }
+
+            impl #name {
+                /// Sets the value at the given dotted key, such as `options.output.format`, parsing the value
+                /// according to the type of that key. The value is left unchanged if it is rejected. Strings can be
+                /// given as raw text, like `JSON`, or quoted like in TOML, like `"JSON"`.
+                pub fn try_set(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::toml_schema::Error> {
+                    ::toml_schema::__private::SetKey::set_key(self, name, value, "").map(|_undo| ())
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,326 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    docs::Docs,
+    item::{ChildItem, Item},
+    table_attributes::TableAttributes,
+    toml_struct::TomlStruct,
+    type_name::TypeName,
+    TableContents,
+};
+
+#[derive(Clone)]
+pub(crate) struct Table {
+    heading: syn::Ident,
+    items: Vec<Item>,
+
+    /// The super-tables named before the last segment of a dotted heading such as `[a.b.c]`. These are resolved,
+    /// and this emptied, when the table is inserted into the document.
+    pub supertables: Vec<syn::Ident>,
+
+    kind: TableKind,
+    pub attributes: TableAttributes,
+}
+
+/// How a table was defined, which determines how it may be extended afterwards.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TableKind {
+    /// Only named as a super-table by a dotted heading, so it may still be declared with its own heading.
+    Implicit,
+
+    /// Declared with a `[heading]`, which can only happen once.
+    Heading,
+
+    /// Created by dotted keys such as `output.format = "JSON"`, which may be extended by further dotted keys.
+    Dotted,
+}
+
+impl syn::parse::Parse for Table {
+    fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+        let heading;
+        syn::bracketed!(heading in input);
+        let (supertables, heading) = parse_heading(&heading)?;
+        let contents: TableContents = input.parse()?;
+
+        Ok(Table {
+            heading,
+            items: contents.0.into_iter().map(Item::from).collect(),
+            supertables,
+            kind: TableKind::Heading,
+            attributes: TableAttributes::default(),
+        })
+    }
+}
+
+/// Parses a possibly dotted heading, returning the names of its super-tables and its last segment.
+pub(crate) fn parse_heading(
+    input: syn::parse::ParseStream,
+) -> syn::Result<(Vec<syn::Ident>, syn::Ident)> {
+    let mut segments =
+        syn::punctuated::Punctuated::<syn::Ident, syn::Token![.]>::parse_separated_nonempty(input)?
+            .into_iter()
+            .collect::<Vec<_>>();
+    if !input.is_empty() {
+        return Err(input.error("Expected the end of the heading"));
+    }
+    let heading = segments.pop().unwrap();
+    Ok((segments, heading))
+}
+
+impl Table {
+    /// Creates an empty table for a super-table that is only named by a dotted heading.
+    pub fn implicit(heading: syn::Ident) -> Self {
+        Table {
+            heading,
+            items: Vec::new(),
+            supertables: Vec::new(),
+            kind: TableKind::Implicit,
+            attributes: TableAttributes::default(),
+        }
+    }
+
+    /// Creates a table for the properties under a dotted key, such as `output` in `output.format = "JSON"`.
+    pub fn dotted(heading: syn::Ident, items: Vec<Item>) -> Self {
+        Table {
+            heading,
+            items,
+            supertables: Vec::new(),
+            kind: TableKind::Dotted,
+            attributes: TableAttributes::default(),
+        }
+    }
+
+    pub fn heading(&self) -> &syn::Ident {
+        &self.heading
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
+    }
+
+    /// Whether this table can be left out, because every item in it has a default value.
+    pub fn has_default(&self) -> bool {
+        self.items.iter().all(Item::has_default)
+    }
+
+    /// Merges another definition of this table into it, as long as the two may define the same table.
+    pub fn merge(&mut self, table: Table) -> syn::Result<()> {
+        let name = &table.heading;
+        match (self.kind, table.kind) {
+            (TableKind::Implicit, kind) => self.kind = kind,
+            (TableKind::Dotted, TableKind::Dotted) => {}
+            (TableKind::Heading, TableKind::Dotted) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Table `{name}` is declared with a heading and cannot be extended with dotted keys"),
+                ))
+            }
+            (TableKind::Dotted, _) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Table `{name}` is already defined by dotted keys"),
+                ))
+            }
+            (TableKind::Heading, _) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Table `{name}` is declared more than once"),
+                ))
+            }
+        }
+
+        self.attributes.merge(table.attributes);
+        for item in table.items {
+            crate::document::insert_item(&mut self.items, item)?;
+        }
+        Ok(())
+    }
+
+    pub fn into_child_table(self, parent_name: TypeName) -> ChildTable {
+        ChildTable {
+            table: self,
+            parent_name,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildTable {
+    table: Table,
+    parent_name: TypeName,
+}
+
+impl ChildTable {
+    pub fn name(&self) -> &syn::Ident {
+        &self.table.heading
+    }
+
+    pub fn docs(&self) -> &Docs {
+        &self.table.attributes.docs
+    }
+
+    pub fn type_name(&self) -> TypeName {
+        self.parent_name.child(&self.table.heading)
+    }
+
+    pub fn has_default(&self) -> bool {
+        self.table.has_default()
+    }
+
+    /// The items of this table, as items of the struct generated for it.
+    pub fn items(&self) -> Vec<ChildItem> {
+        let heading_type = self.type_name();
+        self.table
+            .items
+            .iter()
+            .map(|item| item.clone().into_child_item(heading_type.clone()))
+            .collect()
+    }
+
+    pub fn default_value(&self) -> proc_macro2::TokenStream {
+        let heading_type = self.type_name();
+
+        let properties = self
+            .items()
+            .into_iter()
+            .map(|item| item.into_item_default())
+            .collect::<Vec<_>>();
+
+        quote::quote! {
+            #heading_type {
+                #(
+                    #properties
+                ),*
+            }
+        }
+    }
+}
+
+impl quote::ToTokens for ChildTable {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading_type = self.type_name();
+        let heading = &self.table.heading;
+        tokens.extend(quote::quote! {
+            #heading: #heading_type
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining the struct for the
+/// type of this table.
+#[derive(Clone)]
+pub struct ChildTablePrelude(pub ChildTable);
+
+impl std::ops::Deref for ChildTablePrelude {
+    type Target = ChildTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildTablePrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let items = self.items();
+
+        let name = self.type_name();
+        let prelude = items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_prelude())
+            .collect::<Vec<_>>();
+        let prelude = name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        let toml_struct = TomlStruct {
+            name,
+            items,
+            attributes: self.table.attributes.clone(),
+        };
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #toml_struct
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct ChildTableDefault(pub ChildTable);
+
+impl std::ops::Deref for ChildTableDefault {
+    type Target = ChildTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildTableDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.table.heading.clone();
+        let default = self.default_value();
+
+        tokens.extend(quote::quote! {
+            #heading: #default
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the field initializer that
+/// reads this table out of a parsed TOML table, falling back to its default.
+#[derive(Clone)]
+pub struct ChildTableFromToml(pub ChildTable);
+
+impl std::ops::Deref for ChildTableFromToml {
+    type Target = ChildTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildTableFromToml {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.table.heading.clone();
+        let key = heading.unraw().to_string();
+
+        tokens.extend(quote::quote! {
+            #heading: ::toml_schema::__private::get_table(table, #key, path)?
+        });
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for the getter method of this
+/// table.
+#[derive(Clone)]
+pub struct ChildTableGetter(pub ChildTable);
+
+impl std::ops::Deref for ChildTableGetter {
+    type Target = ChildTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl quote::ToTokens for ChildTableGetter {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let heading = self.table.heading.clone();
+        let heading_type = self.type_name();
+
+        tokens.extend(quote::quote! {
+            pub fn #heading(&self) -> &#heading_type {
+                &self.#heading
+            }
+        });
+    }
+}
@@ -0,0 +1,187 @@
+use crate::docs::Docs;
+
+/// The attributes of a table, given before its heading like `#[validate = my_crate::check_workers]`, or in the
+/// document metadata for the document itself.
+#[derive(Clone, Default)]
+pub(crate) struct TableAttributes {
+    /// Functions that check the struct generated for the table after it is parsed and after each `try_set`.
+    pub validators: Vec<syn::Path>,
+
+    /// The doc comments given with `///` before the heading.
+    pub docs: Docs,
+
+    /// The traits to derive for the generated struct, given with `derive(Debug, Clone)`. Tables that don't give
+    /// their own take those of the enclosing table, or of the document.
+    pub derives: Option<Vec<syn::Path>>,
+
+    /// Any other attributes for the generated struct, given with `attr(...)` like
+    /// `attr(serde(rename_all = "kebab-case"))`. These are inherited in the same way.
+    pub passed_through: Option<Vec<syn::Meta>>,
+
+    /// The visibility of the generated struct, given with `visibility(pub(crate))`. It is `pub` unless given, and
+    /// is inherited in the same way.
+    pub visibility: Option<syn::Visibility>,
+
+    /// The visibility of the fields of the generated struct, given with `field_visibility(pub)`. Fields are
+    /// private unless given, and have getters either way.
+    pub field_visibility: Option<syn::Visibility>,
+}
+
+impl TableAttributes {
+    /// Parses the attributes given before a table heading.
+    pub fn from_attributes(mut attributes: Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut table_attributes = TableAttributes {
+            docs: Docs::take(&mut attributes)?,
+            ..TableAttributes::default()
+        };
+        for attribute in attributes {
+            table_attributes.set_meta(attribute.meta)?;
+        }
+        Ok(table_attributes)
+    }
+
+    /// Sets an attribute written like `validate = path::to::function`, `derive(Debug)` or `visibility(pub(crate))`,
+    /// which is also used for the document metadata.
+    pub fn set_meta(&mut self, meta: syn::Meta) -> syn::Result<()> {
+        match meta {
+            syn::Meta::NameValue(syn::MetaNameValue { path, value, .. }) => self.set(path, value),
+            syn::Meta::List(list) if list.path.is_ident("derive") => {
+                let derives = list.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+                )?;
+                // `Default` is implemented from the defaults in the schema instead
+                if let Some(default) = derives.iter().find(|derive| {
+                    derive
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Default")
+                }) {
+                    return Err(syn::Error::new_spanned(
+                        default,
+                        "`Default` is generated from the defaults in the schema, so it can't be derived",
+                    ));
+                }
+                self.derives.get_or_insert_with(Vec::new).extend(derives);
+                Ok(())
+            }
+            syn::Meta::List(list) if list.path.is_ident("attr") => {
+                let attributes = list.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                )?;
+                self.passed_through
+                    .get_or_insert_with(Vec::new)
+                    .extend(attributes);
+                Ok(())
+            }
+            syn::Meta::List(list) if list.path.is_ident("visibility") => {
+                self.visibility = Some(list.parse_args()?);
+                Ok(())
+            }
+            syn::Meta::List(list) if list.path.is_ident("field_visibility") => {
+                self.field_visibility = Some(list.parse_args()?);
+                Ok(())
+            }
+            meta => Err(unknown_attribute(meta.path())),
+        }
+    }
+
+    /// Sets the attribute with the given name.
+    pub fn set(&mut self, name: syn::Path, value: syn::Expr) -> syn::Result<()> {
+        if name.is_ident("validate") {
+            let syn::Expr::Path(syn::ExprPath { path, .. }) = value else {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    "Expected the path of a function",
+                ));
+            };
+            self.validators.push(path);
+            return Ok(());
+        }
+
+        Err(unknown_attribute(&name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+            && self.docs.is_empty()
+            && self.derives.is_none()
+            && self.passed_through.is_none()
+            && self.visibility.is_none()
+            && self.field_visibility.is_none()
+    }
+
+    /// Adds the attributes given on another declaration of the same table.
+    pub fn merge(&mut self, attributes: TableAttributes) {
+        self.validators.extend(attributes.validators);
+        self.docs.extend(attributes.docs);
+        if let Some(derives) = attributes.derives {
+            self.derives.get_or_insert_with(Vec::new).extend(derives);
+        }
+        if let Some(passed_through) = attributes.passed_through {
+            self.passed_through
+                .get_or_insert_with(Vec::new)
+                .extend(passed_through);
+        }
+        if attributes.visibility.is_some() {
+            self.visibility = attributes.visibility;
+        }
+        if attributes.field_visibility.is_some() {
+            self.field_visibility = attributes.field_visibility;
+        }
+    }
+
+    /// Takes the derives, other attributes and visibilities of the enclosing table, unless this gives its own.
+    pub fn inherit(&mut self, parent: &TableAttributes) {
+        if self.derives.is_none() {
+            self.derives = parent.derives.clone();
+        }
+        if self.passed_through.is_none() {
+            self.passed_through = parent.passed_through.clone();
+        }
+        if self.visibility.is_none() {
+            self.visibility = parent.visibility.clone();
+        }
+        if self.field_visibility.is_none() {
+            self.field_visibility = parent.field_visibility.clone();
+        }
+    }
+
+    pub fn derives(&self) -> &[syn::Path] {
+        self.derives.as_deref().unwrap_or_default()
+    }
+
+    pub fn visibility(&self) -> syn::Visibility {
+        self.visibility
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote! { pub })
+    }
+
+    pub fn field_visibility(&self) -> syn::Visibility {
+        self.field_visibility
+            .clone()
+            .unwrap_or(syn::Visibility::Inherited)
+    }
+
+    /// The attributes of the generated struct, other than its doc comments.
+    pub fn struct_attributes(&self) -> proc_macro2::TokenStream {
+        let derives = self.derives();
+        let passed_through = self.passed_through.as_deref().unwrap_or_default();
+        let derive = (!derives.is_empty()).then(|| quote::quote! { #[derive(#(#derives),*)] });
+        quote::quote! {
+            #derive
+            #(
+                #[#passed_through]
+            )*
+        }
+    }
+}
+
+fn unknown_attribute(name: &syn::Path) -> syn::Error {
+    syn::Error::new_spanned(
+        name,
+        format!(
+            "Unknown attribute `{}`",
+            quote::quote! { #name }.to_string().replace(' ', "")
+        ),
+    )
+}
@@ -0,0 +1,359 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    inline_table::InlineTable,
+    property::{
+        ChildProperty, ChildPropertyDefault, ChildPropertyFromToml, ChildPropertyPrelude, Property,
+    },
+    table_attributes::TableAttributes,
+    type_name::TypeName,
+    variant_name, Value,
+};
+
+/// Alternative inline tables selected by a tag, written as
+/// `{ kind = "file", path = "out.txt" } | { kind = "stdout", color = true }`, which generates an enum with a
+/// struct-like variant for each. The tag is the first key of every alternative.
+#[derive(Clone)]
+pub(crate) struct TaggedUnion {
+    pub tag: syn::Ident,
+    pub alternatives: Vec<Alternative>,
+
+    /// The derives and visibility of the generated enum, which are those of the enclosing table.
+    pub attributes: TableAttributes,
+}
+
+#[derive(Clone)]
+pub(crate) struct Alternative {
+    /// The value of the tag that selects this alternative.
+    pub tag_value: syn::LitStr,
+
+    /// The keys of this alternative other than the tag.
+    pub properties: Vec<Property>,
+}
+
+impl TaggedUnion {
+    /// Creates a tagged union from its alternatives, along with the span of each.
+    pub fn new(tables: Vec<(InlineTable, proc_macro2::Span)>) -> syn::Result<Self> {
+        let mut tag: Option<syn::Ident> = None;
+        let mut alternatives = Vec::<Alternative>::new();
+        for (table, span) in tables {
+            let mut properties = table.properties.into_iter();
+            let Some(Property {
+                name,
+                value: Some(Value::String(tag_value)),
+                explicit_type: None,
+                ..
+            }) = properties.next()
+            else {
+                return Err(syn::Error::new(
+                    span,
+                    "Each alternative must start with the same tag key set to a string",
+                ));
+            };
+            match &tag {
+                Some(tag) if tag != &name => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("Expected the tag `{tag}` as the first key of this alternative"),
+                    ))
+                }
+                Some(_) => {}
+                None => tag = Some(name),
+            }
+
+            let variant = variant_name(&tag_value)?;
+            if let Some(existing) = alternatives
+                .iter()
+                .find(|existing| variant_name(&existing.tag_value).unwrap() == variant)
+            {
+                return Err(syn::Error::new(
+                    tag_value.span(),
+                    format!(
+                        "`{}` has the same variant name `{variant}` as `{}`",
+                        tag_value.value(),
+                        existing.tag_value.value()
+                    ),
+                ));
+            }
+
+            alternatives.push(Alternative {
+                tag_value,
+                properties: properties.collect(),
+            });
+        }
+
+        Ok(TaggedUnion {
+            tag: tag.unwrap(),
+            alternatives,
+            attributes: TableAttributes::default(),
+        })
+    }
+
+    /// Whether this can be left out, because every key of its first alternative, which is its default, has a
+    /// default value.
+    pub fn has_default(&self) -> bool {
+        self.alternatives[0]
+            .properties
+            .iter()
+            .all(Property::has_default)
+    }
+}
+
+/// A tagged union along with the name of the enum generated for it.
+pub struct ChildTaggedUnion {
+    pub union: TaggedUnion,
+    pub name: TypeName,
+}
+
+impl ChildTaggedUnion {
+    /// The variants of the generated enum, along with the tag value and properties of each. The properties are
+    /// named as if they were in a struct named after the enum and variant, like `ConfigOutputFile`.
+    fn variants(&self) -> Vec<(syn::Ident, &syn::LitStr, Vec<ChildProperty>)> {
+        self.union
+            .alternatives
+            .iter()
+            .map(|alternative| {
+                let variant = variant_name(&alternative.tag_value).unwrap();
+                let parent_name = self.name.child(&variant);
+                let properties = alternative
+                    .properties
+                    .iter()
+                    .map(|property| property.clone().into_child_property(parent_name.clone()))
+                    .collect();
+                (variant, &alternative.tag_value, properties)
+            })
+            .collect()
+    }
+
+    /// The default value, which is the first alternative with its defaults.
+    pub fn default_value(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let (variant, _, properties) = self.variants().swap_remove(0);
+        let defaults = properties.into_iter().map(ChildPropertyDefault);
+        quote::quote! {
+            #name::#variant {
+                #(
+                    #defaults
+                ),*
+            }
+        }
+    }
+}
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining the enum generated for
+/// a tagged union, along with the runtime support implemented on it.
+pub struct ChildTaggedUnionPrelude(pub ChildTaggedUnion);
+
+impl quote::ToTokens for ChildTaggedUnionPrelude {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = &self.0.name.ident;
+        let tag = self.0.union.tag.unraw().to_string();
+        let variants = self.0.variants();
+        let visibility = self
+            .0
+            .name
+            .visibility(&self.0.union.attributes.visibility());
+        let derives = self.0.union.attributes.derives();
+        let derive = (!derives.is_empty()).then(|| quote::quote! { #[derive(#(#derives),*)] });
+
+        let tag_values = variants
+            .iter()
+            .map(|(_, tag_value, _)| *tag_value)
+            .collect::<Vec<_>>();
+        let variant_names = variants
+            .iter()
+            .map(|(variant, _, _)| variant)
+            .collect::<Vec<_>>();
+
+        let mut prelude = Vec::new();
+        let mut definitions = Vec::new();
+        let mut from_toml = Vec::new();
+        let mut to_toml = Vec::new();
+        let mut set_key = Vec::new();
+        for (variant, tag_value, properties) in &variants {
+            let fields = properties.iter().map(|property| &property.property.name);
+            let field_names = fields.clone().collect::<Vec<_>>();
+            let keys = field_names
+                .iter()
+                .map(|name| name.unraw().to_string())
+                .collect::<Vec<_>>();
+
+            let docs = properties.iter().map(|property| &property.property.docs);
+            let property_prelude = properties.iter().cloned().map(ChildPropertyPrelude);
+            prelude.push(self.0.name.child(variant).child_module(quote::quote! {
+                #(
+                    #property_prelude
+                )*
+            }));
+            definitions.push(quote::quote! {
+                #variant {
+                    #(
+                        #docs
+                        #properties
+                    ),*
+                }
+            });
+
+            let field_inits = properties.iter().cloned().map(ChildPropertyFromToml);
+            from_toml.push(quote::quote! {
+                #tag_value => {
+                    ::toml_schema::__private::deny_unknown_keys(table, &[#tag, #(#keys),*], path)?;
+                    ::core::result::Result::Ok(#name::#variant {
+                        #(
+                            #field_inits
+                        ),*
+                    })
+                }
+            });
+
+            to_toml.push(quote::quote! {
+                #name::#variant { #(#field_names),* } => {
+                    ::toml_schema::__private::write_inline_table(out, &[
+                        (#tag, &#tag_value.to_owned() as &dyn ::toml_schema::__private::ToToml),
+                        #(
+                            (#keys, #field_names as &dyn ::toml_schema::__private::ToToml)
+                        ),*
+                    ]);
+                }
+            });
+
+            let set_fields = properties.iter().map(|property| {
+                let field = &property.property.name;
+                let key = field.unraw().to_string();
+                let nested = matches!(
+                    property.property.value,
+                    Some(Value::InlineTable(_) | Value::Union(_))
+                )
+                .then(|| {
+                    quote::quote! {
+                        (#name::#variant { #field, .. }, #key, ::core::option::Option::Some(rest)) => {
+                            let undo = ::toml_schema::__private::SetKey::set_key(#field, rest, value, &field_path)?;
+                            ::core::result::Result::Ok(::std::boxed::Box::new(move |this: &mut Self| {
+                                if let #name::#variant { #field, .. } = this {
+                                    undo(#field);
+                                }
+                            }))
+                        }
+                    }
+                });
+                let checks = property.runtime_checks(quote::quote! { &field_path });
+                let integer_checks = property.integer_checks(
+                    quote::quote! { ::toml_schema::__private::integer_str(value) },
+                    quote::quote! { &field_path },
+                );
+                let field_type = property.field_type();
+                quote::quote! {
+                    (#name::#variant { #field, .. }, #key, ::core::option::Option::None) => {
+                        #integer_checks
+                        let value: #field_type = ::toml_schema::__private::FromToml::from_toml_str(value, &field_path)?;
+                        #checks
+                        let previous = ::core::mem::replace(#field, value);
+                        ::core::result::Result::Ok(::std::boxed::Box::new(move |this: &mut Self| {
+                            if let #name::#variant { #field, .. } = this {
+                                *#field = previous;
+                            }
+                        }))
+                    }
+                    #nested
+                }
+            });
+            set_key.extend(set_fields);
+        }
+
+        let prelude = self.0.name.child_module(quote::quote! {
+            #(
+                #prelude
+            )*
+        });
+
+        tokens.extend(quote::quote! {
+            #prelude
+
+            #derive
+            #visibility enum #name {
+                #(
+                    #definitions
+                ),*
+            }
+
+            impl #name {
+                /// The values of the tag, which select between the variants of this enum.
+                pub const TAGS: &'static [&'static str] = &[#(#tag_values),*];
+
+                /// The value of the tag for this variant.
+                pub fn tag(&self) -> &'static str {
+                    match self {
+                        #(
+                            #name::#variant_names { .. } => #tag_values
+                        ),*
+                    }
+                }
+            }
+
+            impl ::toml_schema::__private::FromToml for #name {
+                fn from_toml(
+                    value: &::toml_schema::__private::Value,
+                    path: &str,
+                ) -> ::core::result::Result<Self, ::toml_schema::Error> {
+                    let table = ::toml_schema::__private::expect_table(value, path)?;
+                    let tag: String = ::toml_schema::__private::get_required(table, #tag, path)?;
+                    match tag.as_str() {
+                        #(
+                            #from_toml
+                        )*
+                        _ => ::core::result::Result::Err(::toml_schema::Error::InvalidVariant {
+                            path: ::toml_schema::__private::join(path, #tag),
+                            value: tag,
+                            expected: #name::TAGS,
+                        }),
+                    }
+                }
+            }
+
+            impl ::toml_schema::__private::SetKey for #name {
+                fn set_key(
+                    &mut self,
+                    key: &str,
+                    value: &str,
+                    path: &str,
+                ) -> ::core::result::Result<::toml_schema::__private::Undo<Self>, ::toml_schema::Error> {
+                    let (head, rest) = ::toml_schema::__private::split_key(key);
+                    let field_path = ::toml_schema::__private::join(path, head);
+                    match (self, head, rest) {
+                        // Changing the tag switches to the defaults of the selected variant
+                        (this, #tag, ::core::option::Option::None) => {
+                            let tag: String = ::toml_schema::__private::FromToml::from_toml_str(value, &field_path)?;
+                            if tag == this.tag() {
+                                return ::core::result::Result::Ok(::std::boxed::Box::new(|_this: &mut Self| {}));
+                            }
+                            let mut table = ::toml_schema::__private::Table::new();
+                            table.insert(#tag.to_owned(), ::toml_schema::__private::Value::String(tag));
+                            let value = ::toml_schema::__private::FromToml::from_toml(
+                                &::toml_schema::__private::Value::Table(table),
+                                path,
+                            )?;
+                            let previous = ::core::mem::replace(this, value);
+                            ::core::result::Result::Ok(::std::boxed::Box::new(move |this: &mut Self| *this = previous))
+                        }
+                        #(
+                            #set_key
+                        )*
+                        _ => ::core::result::Result::Err(::toml_schema::Error::UnknownKey {
+                            path: ::toml_schema::__private::join(path, key),
+                        }),
+                    }
+                }
+            }
+
+            impl ::toml_schema::__private::ToToml for #name {
+                fn write_inline(&self, out: &mut String) {
+                    match self {
+                        #(
+                            #to_toml
+                        )*
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,138 @@
+use syn::ext::IdentExt as _;
+
+use crate::{
+    item::ChildItem,
+    struct_impls::{StructField, StructImpls},
+    table_attributes::TableAttributes,
+    type_name::TypeName,
+};
+
+/// The implementation for `quote::ToTokens` for this struct creates the tokens for defining a generated struct
+/// with the given items as fields, along with the runtime support implemented on it.
+pub struct TomlStruct {
+    pub name: TypeName,
+    pub items: Vec<ChildItem>,
+    pub attributes: TableAttributes,
+}
+
+impl quote::ToTokens for TomlStruct {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = &self.name.ident;
+        let fields = &self.items;
+        let docs = &self.attributes.docs;
+        let struct_attributes = self.attributes.struct_attributes();
+        let visibility = self.name.visibility(&self.attributes.visibility());
+        let field_visibility = self.name.visibility(&self.attributes.field_visibility());
+        let field_docs = self.items.iter().map(ChildItem::docs).collect::<Vec<_>>();
+
+        // Structs where every item has a default implement `Default`, and the rest get a constructor that takes
+        // the items without one
+        let constructor = if self.items.iter().all(|item| item.has_default()) {
+            let defaults = self
+                .items
+                .clone()
+                .into_iter()
+                .map(|item| item.into_item_default())
+                .collect::<Vec<_>>();
+            quote::quote! {
+                impl ::core::default::Default for #name {
+                    fn default() -> Self {
+                        #name {
+                            #(
+                                #defaults
+                            ),*
+                        }
+                    }
+                }
+            }
+        } else {
+            let (required, defaulted): (Vec<_>, Vec<_>) =
+                self.items.iter().partition(|item| !item.has_default());
+            let required_names = required.iter().map(|item| item.name()).collect::<Vec<_>>();
+            let required_types = required.iter().map(|item| item.field_type());
+            let defaults = defaulted
+                .into_iter()
+                .map(|item| item.clone().into_item_default())
+                .collect::<Vec<_>>();
+            quote::quote! {
+                impl #name {
+                    /// Creates this with the given values for the keys that don't have a default.
+                    pub fn new(#(#required_names: #required_types),*) -> Self {
+                        #name {
+                            #(
+                                #required_names,
+                            )*
+                            #(
+                                #defaults
+                            ),*
+                        }
+                    }
+                }
+            }
+        };
+
+        let getters = self
+            .items
+            .clone()
+            .into_iter()
+            .map(|item| item.into_item_getter())
+            .collect::<Vec<_>>();
+
+        let struct_fields = self
+            .items
+            .iter()
+            .map(|item| {
+                let name = item.name();
+                let from_toml = item.clone().into_item_from_toml();
+                StructField {
+                    name: name.clone(),
+                    key: name.unraw().to_string(),
+                    docs: item.docs().lines(),
+                    field_type: item.field_type(),
+                    is_section: item.is_section(),
+                    is_table: item.is_table(),
+                    from_toml: quote::quote! { #from_toml },
+                    read: {
+                        let integer_checks = item.integer_checks(
+                            quote::quote! { ::toml_schema::__private::integer_str(value) },
+                            quote::quote! { &field_path },
+                        );
+                        quote::quote! {{
+                            #integer_checks
+                            ::toml_schema::__private::FromToml::from_toml_str(value, &field_path)?
+                        }}
+                    },
+                    checks: item.runtime_checks(quote::quote! { &field_path }),
+                }
+            })
+            .collect();
+        let impls = StructImpls {
+            name,
+            fields: struct_fields,
+            attributes: &self.attributes,
+            before_from_toml: quote::quote! {},
+        };
+
+        tokens.extend(quote::quote! {
+            #docs
+            #struct_attributes
+            #visibility struct #name {
+                #(
+                    #field_docs
+                    #field_visibility #fields
+                ),*
+            }
+
+            #constructor
+
+            #impls
+
+            impl #name {
+                #(
+                    #field_docs
+                    #getters
+                )*
+            }
+        });
+    }
+}
@@ -0,0 +1,314 @@
+use convert_case::Casing as _;
+use syn::ext::IdentExt as _;
+
+use crate::{item::Item, property::Property, Value};
+
+/// The name of a generated struct or enum. The implementation for `quote::ToTokens` gives its path from the scope
+/// the `toml!` macro is invoked in, which every generated module imports with `use super::*`, so that the path
+/// works from any of them.
+#[derive(Clone)]
+pub(crate) struct TypeName {
+    pub ident: syn::Ident,
+
+    /// The modules the type is defined in, like `config` and `options` for `config::options::Output`. This is
+    /// empty unless the document is generated as a module tree.
+    pub modules: Vec<syn::Ident>,
+
+    /// Whether the types of the children of this one are defined in a module named after it, like
+    /// `config::options::Output`, rather than named by joining the names, like `ConfigOptionsOutput`.
+    pub nested: bool,
+}
+
+impl TypeName {
+    pub fn root(ident: syn::Ident, nested: bool) -> Self {
+        TypeName {
+            ident,
+            modules: Vec::new(),
+            nested,
+        }
+    }
+
+    /// The name of the type generated for a table, key or tagged union variant within this one, which is its
+    /// own name in Pascal case, after the name of this type unless it is in a module of its own.
+    pub fn child(&self, name: &syn::Ident) -> TypeName {
+        let name = name.unraw().to_string().to_case(convert_case::Case::Pascal);
+        if !self.nested {
+            return TypeName {
+                ident: quote::format_ident!("{}{}", self.ident, name),
+                modules: Vec::new(),
+                nested: false,
+            };
+        }
+
+        let mut modules = self.modules.clone();
+        modules.push(self.module());
+        TypeName {
+            ident: syn::Ident::new(&name, self.ident.span()),
+            modules,
+            nested: true,
+        }
+    }
+
+    /// This name followed by a suffix, like `ConfigServersEntry` for the entries of the map `ConfigServers`.
+    pub fn suffixed(&self, suffix: &str) -> TypeName {
+        TypeName {
+            ident: quote::format_ident!("{}{}", self.ident, suffix),
+            ..self.clone()
+        }
+    }
+
+    /// The module the types of the children of this one are defined in, which is its name in snake case.
+    fn module(&self) -> syn::Ident {
+        let module = self.ident.to_string().to_case(convert_case::Case::Snake);
+        match syn::parse_str::<syn::Ident>(&module) {
+            Ok(_) => syn::Ident::new(&module, self.ident.span()),
+            Err(_) => syn::Ident::new_raw(&module, self.ident.span()),
+        }
+    }
+
+    /// Puts the definitions of the types of the children of this one in the module for them, if the document is
+    /// generated as a module tree.
+    pub fn child_module(&self, definitions: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if !self.nested || definitions.is_empty() {
+            return definitions;
+        }
+
+        let module = self.module();
+        quote::quote! {
+            pub mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #definitions
+            }
+        }
+    }
+
+    /// Adjusts a visibility given for the scope of the `toml!` invocation to the module this type is defined in,
+    /// so that private items are still visible to the structs of the enclosing tables.
+    pub fn visibility(&self, visibility: &syn::Visibility) -> syn::Visibility {
+        if self.modules.is_empty() {
+            return visibility.clone();
+        }
+
+        let supers = self.modules.iter().map(|_| quote::quote! { super });
+        let path = match visibility {
+            syn::Visibility::Inherited => quote::quote! { #(#supers)::* },
+            syn::Visibility::Restricted(restricted) if restricted.path.is_ident("self") => {
+                quote::quote! { #(#supers)::* }
+            }
+            syn::Visibility::Restricted(restricted)
+                if restricted
+                    .path
+                    .segments
+                    .first()
+                    .is_some_and(|segment| segment.ident == "super") =>
+            {
+                let path = &restricted.path;
+                quote::quote! { #(#supers::)* #path }
+            }
+            visibility => return visibility.clone(),
+        };
+        syn::parse_quote! { pub(in #path) }
+    }
+
+    fn path(&self) -> String {
+        let mut path = self
+            .modules
+            .iter()
+            .map(|module| format!("{}::", module.unraw()))
+            .collect::<String>();
+        path.push_str(&self.ident.to_string());
+        path
+    }
+}
+
+impl quote::ToTokens for TypeName {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let modules = &self.modules;
+        let ident = &self.ident;
+        tokens.extend(quote::quote! { #(#modules::)* #ident });
+    }
+}
+
+/// The names of the types generated for a document, and the modules they are defined in, which are checked for
+/// collisions as they are added. Joined names can collide, such as `[a_b]` and `b` in `[a]` which would both
+/// generate `ConfigAB`, and so can keys that share a name with a generated method.
+pub(crate) struct Names {
+    /// The paths of the generated types, along with the key that each was generated for.
+    types: Vec<(String, syn::Ident)>,
+
+    /// The paths of the generated modules, along with the path of the type each holds the children of.
+    modules: Vec<(String, String)>,
+
+    /// The module of the document, which a generated module with the same name would hide.
+    root_module: Option<String>,
+}
+
+/// Types that the generated code refers to by name, which generated types in a module must not hide.
+const PRELUDE_TYPES: &[&str] = &["Option", "Result", "String", "Vec", "Box"];
+
+impl Names {
+    /// Checks the names generated for the items of a document with a struct of the given name.
+    pub fn check_document(root: &TypeName, items: &[Item]) -> syn::Result<()> {
+        let mut names = Names {
+            types: Vec::new(),
+            modules: Vec::new(),
+            root_module: root.nested.then(|| root.module().unraw().to_string()),
+        };
+        names.check_methods(
+            items.iter().map(|item| (item.name(), item.has_default())),
+            true,
+        )?;
+        names.check_items(root, items)
+    }
+
+    fn check_items(&mut self, parent: &TypeName, items: &[Item]) -> syn::Result<()> {
+        for item in items {
+            let (name, items) = match item {
+                Item::Table(table) => (parent.child(table.heading()), table.items()),
+                Item::ArrayOfTables(array) => (parent.child(array.heading()), array.items()),
+                Item::MapTable(map) => (parent.child(map.heading()).suffixed("Entry"), map.items()),
+                Item::Property(property) => {
+                    self.check_property(parent, property)?;
+                    continue;
+                }
+            };
+            self.add(parent, &name, item.name())?;
+            self.check_methods(
+                items.iter().map(|item| (item.name(), item.has_default())),
+                false,
+            )?;
+            self.check_items(&name, items)?;
+        }
+        Ok(())
+    }
+
+    fn check_property(&mut self, parent: &TypeName, property: &Property) -> syn::Result<()> {
+        match &property.value {
+            Some(Value::InlineTable(table)) => {
+                let name = parent.child(&property.name);
+                self.add(parent, &name, &property.name)?;
+                self.check_methods(
+                    table
+                        .properties
+                        .iter()
+                        .map(|property| (&property.name, property.has_default())),
+                    false,
+                )?;
+                for child in &table.properties {
+                    self.check_property(&name, child)?;
+                }
+            }
+            Some(Value::Enum(_)) => {
+                self.add(parent, &parent.child(&property.name), &property.name)?
+            }
+            Some(Value::Union(union)) => {
+                let name = parent.child(&property.name);
+                self.add(parent, &name, &property.name)?;
+                for alternative in &union.alternatives {
+                    let variant = crate::variant_name(&alternative.tag_value)?;
+                    let variant_name = name.child(&variant);
+                    for child in &alternative.properties {
+                        self.check_property(&variant_name, child)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Adds the type generated for the given key within the given parent type.
+    fn add(&mut self, parent: &TypeName, name: &TypeName, key: &syn::Ident) -> syn::Result<()> {
+        let path = name.path();
+        if let Some((_, existing)) = self.types.iter().find(|(existing, _)| existing == &path) {
+            return Err(syn::Error::new(
+                key.span(),
+                format!(
+                    "`{}` generates the type `{path}`, which is also generated for `{}`",
+                    key.unraw(),
+                    existing.unraw()
+                ),
+            ));
+        }
+        if name.nested && PRELUDE_TYPES.contains(&name.ident.to_string().as_str()) {
+            return Err(syn::Error::new(
+                key.span(),
+                format!(
+                    "`{}` generates the type `{path}`, which would hide `{}` in its module",
+                    key.unraw(),
+                    name.ident
+                ),
+            ));
+        }
+        self.types.push((path, key.clone()));
+
+        if let Some(module) = name.modules.last() {
+            let module_path = name
+                .modules
+                .iter()
+                .map(|module| module.unraw().to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            let parent_path = parent.path();
+            match self
+                .modules
+                .iter()
+                .find(|(existing, _)| existing == &module_path)
+            {
+                Some((_, existing_parent)) if existing_parent != &parent_path => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "`{parent_path}` and `{existing_parent}` would both define their types in the module `{module_path}`"
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    if name.modules.len() > 1
+                        && self.root_module.as_deref() == Some(&*module.unraw().to_string())
+                    {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            format!(
+                                "`{parent_path}` would define its types in the module `{module_path}`, which hides the module `{module}` of the document"
+                            ),
+                        ));
+                    }
+                    self.modules.push((module_path, parent_path));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that none of the keys of a struct share a name with the methods generated for it. Structs with keys
+    /// that don't have a default get a `new` constructor, and the struct of the document gets `sample_toml` and
+    /// `json_schema`.
+    fn check_methods<'a>(
+        &self,
+        keys: impl Iterator<Item = (&'a syn::Ident, bool)>,
+        is_document: bool,
+    ) -> syn::Result<()> {
+        let keys = keys.collect::<Vec<_>>();
+        let has_constructor = keys.iter().any(|(_, has_default)| !has_default);
+        for (key, _) in &keys {
+            let name = key.unraw().to_string();
+            let collides = match name.as_str() {
+                "try_set" => true,
+                "new" => has_constructor,
+                "sample_toml" | "json_schema" => is_document,
+                _ => false,
+            };
+            if collides {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("`{name}` has the same name as a method generated for its struct"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+/// An error produced while reading a TOML document into a type generated by `toml!`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The input is not valid TOML.
+    Syntax(String),
+
+    /// The input is missing a key that the schema declares without a default.
+    MissingKey { path: String },
+
+    /// The input contains a key that the schema does not declare.
+    UnknownKey { path: String },
+
+    /// The value for a key has a different type than the schema declares.
+    InvalidType {
+        path: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    /// A value given as a string, such as to `try_set`, could not be parsed.
+    InvalidValue { path: String, value: String },
+
+    /// A number is outside of the range that the schema allows for its key.
+    OutOfRange {
+        path: String,
+        value: String,
+        range: &'static str,
+    },
+
+    /// A string does not match the pattern that the schema requires for its key.
+    PatternMismatch {
+        path: String,
+        value: String,
+        pattern: &'static str,
+    },
+
+    /// A string has fewer or more characters than the schema allows for its key.
+    InvalidLength {
+        path: String,
+        value: String,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+
+    /// A function given with `#[validate = ...]` rejected the table at `path`, which is empty for the document.
+    Validation { path: String, message: String },
+
+    /// The value for a key that only allows a fixed set of strings is not one of them.
+    InvalidVariant {
+        path: String,
+        value: String,
+        expected: &'static [&'static str],
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Syntax(message) => write!(f, "invalid TOML: {message}"),
+            Error::MissingKey { path } => write!(f, "missing required key `{path}`"),
+            Error::UnknownKey { path } => write!(f, "unknown key `{path}`"),
+            Error::InvalidType {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "invalid type for `{path}`: expected {expected}, found {found}"
+            ),
+            Error::InvalidValue { path, value } => {
+                write!(f, "invalid value for `{path}`: `{value}`")
+            }
+            Error::OutOfRange { path, value, range } => write!(
+                f,
+                "invalid value for `{path}`: `{value}` is not in the range `{range}`"
+            ),
+            Error::PatternMismatch {
+                path,
+                value,
+                pattern,
+            } => write!(
+                f,
+                "invalid value for `{path}`: `{value}` does not match the pattern `{pattern}`"
+            ),
+            Error::InvalidLength {
+                path,
+                value,
+                min,
+                max,
+            } => {
+                write!(f, "invalid value for `{path}`: `{value}` ")?;
+                match (min, max) {
+                    (Some(min), Some(max)) => {
+                        write!(f, "must be between {min} and {max} characters long")
+                    }
+                    (Some(min), None) => write!(f, "must be at least {min} characters long"),
+                    (None, Some(max)) => write!(f, "must be at most {max} characters long"),
+                    (None, None) => write!(f, "has an invalid length"),
+                }
+            }
+            Error::Validation { path, message } if path.is_empty() => {
+                write!(f, "validation failed: {message}")
+            }
+            Error::Validation { path, message } => {
+                write!(f, "validation failed for `{path}`: {message}")
+            }
+            Error::InvalidVariant {
+                path,
+                value,
+                expected,
+            } => {
+                let expected = expected
+                    .iter()
+                    .map(|value| format!("`{value}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if path.is_empty() {
+                    write!(f, "invalid value `{value}`: expected one of {expected}")
+                } else {
+                    write!(
+                        f,
+                        "invalid value for `{path}`: `{value}`, expected one of {expected}"
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,724 @@
+//! Support code for the items generated by `toml!`. Apart from `FromToml` and `ToToml`, which are re-exported so
+//! that other types can be used as explicitly typed keys, nothing in here is public API.
+
+pub use regex::Regex;
+pub use toml::{Table, Value};
+
+use std::{collections::BTreeMap, fmt::Display, ops::RangeBounds, sync::OnceLock};
+
+use crate::Error;
+
+/// Conversion from a parsed TOML value into a schema type.
+pub trait FromToml: Sized {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error>;
+
+    /// Reads a value written as it would appear on the right-hand side of a TOML key, e.g. from a
+    /// `--set key=value` command line override.
+    fn from_toml_str(source: &str, path: &str) -> Result<Self, Error> {
+        Self::from_toml(&parse_value(source, path)?, path)
+    }
+}
+
+/// Assignment of a value given as a string to a (possibly dotted) key of a generated struct.
+pub trait SetKey {
+    /// Sets the key, returning a function that changes it back in case a validator of an enclosing table
+    /// rejects the new value.
+    fn set_key(&mut self, key: &str, value: &str, path: &str) -> Result<Undo<Self>, Error>;
+}
+
+pub type Undo<T> = Box<dyn FnOnce(&mut T)>;
+
+/// Running the validators given for a generated struct with `#[validate = ...]`.
+pub trait Validate {
+    fn validate(&self, path: &str) -> Result<(), Error>;
+}
+
+/// Conversion of a schema type back into TOML text.
+pub trait ToToml {
+    /// Writes this value as it would appear on the right-hand side of a key.
+    fn write_inline(&self, out: &mut String);
+
+    /// Whether the key holding this value is left out entirely, which is the case for unset optional keys.
+    fn is_absent(&self) -> bool {
+        false
+    }
+}
+
+/// Conversion of a generated struct into the body of a TOML table, with nested tables written as sections.
+pub trait ToTomlTable: ToToml {
+    fn write_table(&self, out: &mut String, path: &str);
+}
+
+/// Writing of a value that is stored as one or more `[section]`s rather than as a key-value pair.
+pub trait ToTomlSection {
+    /// Writes the sections, with the given doc comments before the first header.
+    fn write_section(&self, out: &mut String, path: &str, docs: &[&str]);
+}
+
+/// Writing of an annotated sample config for structs deriving `TomlSchema`, whose defaults are only known at
+/// runtime.
+pub trait ToTomlSample {
+    fn write_sample(&self, out: &mut String, path: &str, commented: bool);
+
+    /// Whether the table is unset, in which case its header is commented out along with its keys.
+    fn is_absent(&self) -> bool {
+        false
+    }
+}
+
+impl FromToml for bool {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::Boolean(value) => Ok(*value),
+            other => Err(invalid_type(path, "a boolean", other)),
+        }
+    }
+}
+
+impl FromToml for String {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::String(value) => Ok(value.clone()),
+            other => Err(invalid_type(path, "a string", other)),
+        }
+    }
+
+    /// Strings can be given either as raw text or as a quoted TOML string, which is unquoted.
+    fn from_toml_str(source: &str, _path: &str) -> Result<Self, Error> {
+        if source.starts_with(['"', '\'']) {
+            if let Ok(Value::String(value)) = parse_value(source, "") {
+                return Ok(value);
+            }
+        }
+        Ok(source.to_owned())
+    }
+}
+
+impl FromToml for std::path::PathBuf {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        String::from_toml(value, path).map(Self::from)
+    }
+
+    fn from_toml_str(source: &str, path: &str) -> Result<Self, Error> {
+        String::from_toml_str(source, path).map(Self::from)
+    }
+}
+
+impl ToToml for std::path::PathBuf {
+    fn write_inline(&self, out: &mut String) {
+        self.to_string_lossy().into_owned().write_inline(out);
+    }
+}
+
+macro_rules! float {
+    ($($float: ident),*) => {
+        $(
+            impl FromToml for $float {
+                fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+                    match value {
+                        Value::Float(value) => Ok(*value as $float),
+                        Value::Integer(value) => Ok(*value as $float),
+                        other => Err(invalid_type(path, "a number", other)),
+                    }
+                }
+            }
+
+            /// Finite values are written with their own shortest representation, so that an `f32` is not written
+            /// with the digits it gains from widening to `f64`.
+            impl ToToml for $float {
+                fn write_inline(&self, out: &mut String) {
+                    if !self.is_finite() {
+                        out.push_str(&Value::Float(*self as f64).to_string());
+                        return;
+                    }
+                    let digits = self.to_string();
+                    out.push_str(&digits);
+                    if !digits.contains(['.', 'e', 'E']) {
+                        out.push_str(".0");
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float!(f32, f64);
+
+macro_rules! integer {
+    ($($integer: ident),*) => {
+        $(
+            impl FromToml for $integer {
+                fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+                    match value {
+                        Value::Integer(value) => {
+                            $integer::try_from(*value).map_err(|_error| Error::InvalidValue {
+                                path: path.to_owned(),
+                                value: value.to_string(),
+                            })
+                        }
+                        other => Err(invalid_type(path, "an integer", other)),
+                    }
+                }
+            }
+
+            impl ToToml for $integer {
+                fn write_inline(&self, out: &mut String) {
+                    out.push_str(&self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToToml for bool {
+    fn write_inline(&self, out: &mut String) {
+        out.push_str(&Value::Boolean(*self).to_string());
+    }
+}
+
+impl ToToml for String {
+    fn write_inline(&self, out: &mut String) {
+        out.push_str(&Value::String(self.clone()).to_string());
+    }
+}
+
+impl<T: FromToml> FromToml for Vec<T> {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::Array(elements) => elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| T::from_toml(element, &format!("{path}[{index}]")))
+                .collect(),
+            other => Err(invalid_type(path, "an array", other)),
+        }
+    }
+}
+
+impl<T: ToToml> ToToml for Vec<T> {
+    fn write_inline(&self, out: &mut String) {
+        out.push('[');
+        for (index, element) in self.iter().enumerate() {
+            if index != 0 {
+                out.push_str(", ");
+            }
+            element.write_inline(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: FromToml> FromToml for Option<T> {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        T::from_toml(value, path).map(Some)
+    }
+
+    fn from_toml_str(source: &str, path: &str) -> Result<Self, Error> {
+        T::from_toml_str(source, path).map(Some)
+    }
+}
+
+impl<T: ToToml> ToToml for Option<T> {
+    fn write_inline(&self, out: &mut String) {
+        if let Some(value) = self {
+            value.write_inline(out);
+        }
+    }
+
+    fn is_absent(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T: ToTomlTable> ToTomlSection for Vec<T> {
+    fn write_section(&self, out: &mut String, path: &str, docs: &[&str]) {
+        for (index, table) in self.iter().enumerate() {
+            let docs = if index == 0 { docs } else { &[] };
+            write_header(out, docs, &format!("[[{path}]]"));
+            table.write_table(out, path);
+        }
+    }
+}
+
+impl<T: FromToml> FromToml for BTreeMap<String, T> {
+    fn from_toml(value: &Value, path: &str) -> Result<Self, Error> {
+        expect_table(value, path)?
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), T::from_toml(value, &join(path, key))?)))
+            .collect()
+    }
+}
+
+impl<T: ToToml> ToToml for BTreeMap<String, T> {
+    fn write_inline(&self, out: &mut String) {
+        let keys = self.keys().map(|key| quote_key(key)).collect::<Vec<_>>();
+        let entries = keys
+            .iter()
+            .zip(self.values())
+            .map(|(key, value)| (key.as_str(), value as &dyn ToToml))
+            .collect::<Vec<_>>();
+        write_inline_table(out, &entries);
+    }
+}
+
+impl<T: ToTomlTable> ToTomlSection for BTreeMap<String, T> {
+    fn write_section(&self, out: &mut String, path: &str, docs: &[&str]) {
+        for (index, (key, table)) in self.iter().enumerate() {
+            let docs = if index == 0 { docs } else { &[] };
+            let path = format!("{path}.{}", quote_key(key));
+            write_header(out, docs, &format!("[{path}]"));
+            table.write_table(out, &path);
+        }
+    }
+}
+
+/// Entries that are missing when a key is set through them are created from their defaults.
+impl<T: FromToml + SetKey + 'static> SetKey for BTreeMap<String, T> {
+    fn set_key(&mut self, key: &str, value: &str, path: &str) -> Result<Undo<Self>, Error> {
+        let (head, rest) = split_key(key);
+        let head = head.to_owned();
+        let entry_path = join(path, &head);
+        let Some(rest) = rest else {
+            let previous = self.insert(head.clone(), T::from_toml_str(value, &entry_path)?);
+            return Ok(Box::new(move |this: &mut Self| match previous {
+                Some(previous) => {
+                    this.insert(head, previous);
+                }
+                None => {
+                    this.remove(&head);
+                }
+            }));
+        };
+
+        let created = !self.contains_key(&head);
+        if created {
+            let entry = T::from_toml(&Value::Table(Table::new()), &entry_path)?;
+            self.insert(head.clone(), entry);
+        }
+        match self
+            .get_mut(&head)
+            .unwrap()
+            .set_key(rest, value, &entry_path)
+        {
+            Ok(undo) => Ok(Box::new(move |this: &mut Self| {
+                if created {
+                    this.remove(&head);
+                } else if let Some(entry) = this.get_mut(&head) {
+                    undo(entry);
+                }
+            })),
+            Err(error) => {
+                if created {
+                    self.remove(&head);
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Optional tables are left out while they are unset.
+impl<T: ToTomlSection> ToTomlSection for Option<T> {
+    fn write_section(&self, out: &mut String, path: &str, docs: &[&str]) {
+        if let Some(table) = self {
+            table.write_section(out, path, docs);
+        }
+    }
+}
+
+/// Unset optional tables are created from their defaults when a key is set through them.
+impl<T: FromToml + SetKey + 'static> SetKey for Option<T> {
+    fn set_key(&mut self, key: &str, value: &str, path: &str) -> Result<Undo<Self>, Error> {
+        let created = self.is_none();
+        if created {
+            *self = Some(T::from_toml(&Value::Table(Table::new()), path)?);
+        }
+        match self.as_mut().unwrap().set_key(key, value, path) {
+            Ok(undo) => Ok(Box::new(move |this: &mut Self| {
+                if created {
+                    *this = None;
+                } else if let Some(table) = this {
+                    undo(table);
+                }
+            })),
+            Err(error) => {
+                if created {
+                    *self = None;
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Unset optional tables are shown with their defaults, commented out.
+impl<T: ToTomlSample + Default> ToTomlSample for Option<T> {
+    fn write_sample(&self, out: &mut String, path: &str, commented: bool) {
+        match self {
+            Some(table) => table.write_sample(out, path, commented),
+            None => T::default().write_sample(out, path, true),
+        }
+    }
+
+    fn is_absent(&self) -> bool {
+        self.is_none()
+    }
+}
+
+pub fn parse_document(source: &str) -> Result<Value, Error> {
+    source
+        .parse::<Table>()
+        .map(Value::Table)
+        .map_err(|error| Error::Syntax(error.to_string().trim_end().to_owned()))
+}
+
+pub fn parse_value(source: &str, path: &str) -> Result<Value, Error> {
+    format!("value = {source}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .ok_or_else(|| Error::InvalidValue {
+            path: path.to_owned(),
+            value: source.to_owned(),
+        })
+}
+
+pub fn expect_table<'a>(value: &'a Value, path: &str) -> Result<&'a Table, Error> {
+    match value {
+        Value::Table(table) => Ok(table),
+        other => Err(invalid_type(path, "a table", other)),
+    }
+}
+
+pub fn deny_unknown_keys(table: &Table, keys: &[&str], path: &str) -> Result<(), Error> {
+    match table.keys().find(|key| !keys.contains(&key.as_str())) {
+        Some(key) => Err(Error::UnknownKey {
+            path: join(path, key),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn get<T: FromToml>(table: &Table, key: &str, path: &str) -> Result<Option<T>, Error> {
+    table
+        .get(key)
+        .map(|value| T::from_toml(value, &join(path, key)))
+        .transpose()
+}
+
+/// Splits the first segment off of a dotted key.
+pub fn split_key(key: &str) -> (&str, Option<&str>) {
+    match key.split_once('.') {
+        Some((head, rest)) => (head.trim(), Some(rest)),
+        None => (key.trim(), None),
+    }
+}
+
+pub fn get_required<T: FromToml>(table: &Table, key: &str, path: &str) -> Result<T, Error> {
+    get(table, key, path)?.ok_or_else(|| Error::MissingKey {
+        path: join(path, key),
+    })
+}
+
+/// Reads a nested table, reading it from an empty table if it is missing so that its keys take their defaults.
+pub fn get_table<T: FromToml>(table: &Table, key: &str, path: &str) -> Result<T, Error> {
+    match table.get(key) {
+        Some(value) => T::from_toml(value, &join(path, key)),
+        None => T::from_toml(&Value::Table(Table::new()), &join(path, key)),
+    }
+}
+
+pub fn check_range<T: PartialOrd + std::fmt::Display>(
+    value: &T,
+    range: impl RangeBounds<T>,
+    description: &'static str,
+    path: &str,
+) -> Result<(), Error> {
+    if range.contains(value) {
+        return Ok(());
+    }
+    Err(Error::OutOfRange {
+        path: path.to_owned(),
+        value: value.to_string(),
+        range: description,
+    })
+}
+
+/// The integer in a TOML value given for a key with a range, if it is one. Integers are checked against the range
+/// before they are converted to the type of the key, so that integers that don't fit the type are reported as
+/// outside of the range too, rather than as invalid values.
+pub fn integer(value: Option<&Value>) -> Option<i128> {
+    match value {
+        Some(Value::Integer(value)) => Some(i128::from(*value)),
+        _ => None,
+    }
+}
+
+/// The integer in a string given to `try_set` for a key with a range, like [`integer`]. This can also be a decimal
+/// integer that is too large for TOML, like one that only fits a `u64`.
+pub fn integer_str(source: &str) -> Option<i128> {
+    match parse_value(source, "") {
+        Ok(Value::Integer(value)) => Some(i128::from(value)),
+        _ => source.trim().parse().ok(),
+    }
+}
+
+/// Checks a string against a pattern, which is compiled the first time it is used.
+pub fn check_pattern(
+    value: &str,
+    regex: &OnceLock<Regex>,
+    pattern: &'static str,
+    path: &str,
+) -> Result<(), Error> {
+    let regex = regex.get_or_init(|| Regex::new(pattern).expect("patterns are checked by `toml!`"));
+    if regex.is_match(value) {
+        return Ok(());
+    }
+    Err(Error::PatternMismatch {
+        path: path.to_owned(),
+        value: value.to_owned(),
+        pattern,
+    })
+}
+
+/// Checks the number of characters in a string.
+pub fn check_length(
+    value: &str,
+    min: Option<usize>,
+    max: Option<usize>,
+    path: &str,
+) -> Result<(), Error> {
+    let length = value.chars().count();
+    if min.is_some_and(|min| length < min) || max.is_some_and(|max| length > max) {
+        return Err(Error::InvalidLength {
+            path: path.to_owned(),
+            value: value.to_owned(),
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
+pub fn run_validator<T, E: Display>(
+    value: &T,
+    validator: impl FnOnce(&T) -> Result<(), E>,
+    path: &str,
+) -> Result<(), Error> {
+    validator(value).map_err(|error| Error::Validation {
+        path: path.to_owned(),
+        message: error.to_string(),
+    })
+}
+
+pub fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Writes a key as it appears in TOML, quoting it unless it is a bare key.
+fn quote_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_owned()
+    } else {
+        Value::String(key.to_owned()).to_string()
+    }
+}
+
+fn invalid_type(path: &str, expected: &'static str, found: &Value) -> Error {
+    Error::InvalidType {
+        path: path.to_owned(),
+        expected,
+        found: found.type_str(),
+    }
+}
+
+/// Writes a generated struct as a whole document, starting with the doc comments of the struct.
+pub fn write_document(out: &mut String, docs: &[&str], table: &dyn ToTomlTable) {
+    if !docs.is_empty() {
+        write_comment(out, docs);
+        out.push('\n');
+    }
+    table.write_table(out, "");
+}
+
+pub fn write_key_value(out: &mut String, key: &str, docs: &[&str], value: &dyn ToToml) {
+    if value.is_absent() {
+        return;
+    }
+    write_comment(out, docs);
+    out.push_str(key);
+    out.push_str(" = ");
+    value.write_inline(out);
+    out.push('\n');
+}
+
+pub fn write_inline_table(out: &mut String, entries: &[(&str, &dyn ToToml)]) {
+    let entries = entries
+        .iter()
+        .filter(|(_, value)| !value.is_absent())
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+
+    out.push_str("{ ");
+    for (index, (key, value)) in entries.iter().enumerate() {
+        if index != 0 {
+            out.push_str(", ");
+        }
+        out.push_str(key);
+        out.push_str(" = ");
+        value.write_inline(out);
+    }
+    out.push_str(" }");
+}
+
+pub fn write_section(
+    out: &mut String,
+    path: &str,
+    key: &str,
+    docs: &[&str],
+    section: &dyn ToTomlSection,
+) {
+    section.write_section(out, &join(path, key), docs);
+}
+
+/// Writes a section header, separated from what comes before it by an empty line.
+pub fn write_header(out: &mut String, docs: &[&str], header: &str) {
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+    write_comment(out, docs);
+    out.push_str(header);
+    out.push('\n');
+}
+
+/// The sample config for a struct deriving `TomlSchema`, starting with its doc comments.
+pub fn sample_document<T: ToTomlSample + Default>(docs: &[&str]) -> String {
+    let mut out = String::new();
+    write_comment(&mut out, docs);
+    T::default().write_sample(&mut out, "", false);
+    out
+}
+
+/// Writes a key of a sample config along with its doc comments and type. Unset optional keys are commented out,
+/// with the given placeholder as their value.
+pub fn write_sample_key(
+    out: &mut String,
+    key: &str,
+    docs: &[&str],
+    type_name: &str,
+    value: &dyn ToToml,
+    placeholder: &str,
+    commented: bool,
+) {
+    start_sample_block(out);
+    write_comment(out, docs);
+    write_comment(out, &[&format!("Type: {type_name}")]);
+    if value.is_absent() {
+        out.push_str(&format!("# {key} = {placeholder}\n"));
+        return;
+    }
+    if commented {
+        out.push_str("# ");
+    }
+    out.push_str(key);
+    out.push_str(" = ");
+    value.write_inline(out);
+    out.push('\n');
+}
+
+pub fn write_sample_table(
+    out: &mut String,
+    path: &str,
+    key: &str,
+    docs: &[&str],
+    table: &dyn ToTomlSample,
+    commented: bool,
+) {
+    let path = join(path, key);
+    let commented = commented || table.is_absent();
+    write_sample_header(out, docs, commented, &format!("[{path}]"));
+    table.write_sample(out, &path, commented);
+}
+
+/// Writes an example element of an array of tables, commented out, followed by the elements of its default.
+pub fn write_sample_array<T: ToTomlSample + ToTomlTable + Default>(
+    out: &mut String,
+    path: &str,
+    key: &str,
+    docs: &[&str],
+    entries: &[T],
+    commented: bool,
+) {
+    let path = join(path, key);
+    write_sample_header(out, docs, true, &format!("[[{path}]]"));
+    T::default().write_sample(out, &path, true);
+    if !commented {
+        for entry in entries {
+            write_header(out, &[], &format!("[[{path}]]"));
+            entry.write_table(out, &path);
+        }
+    }
+}
+
+/// Writes an example entry of a map table, commented out, followed by the entries of its default.
+pub fn write_sample_map<T: ToTomlSample + ToTomlTable + Default>(
+    out: &mut String,
+    path: &str,
+    key: &str,
+    docs: &[&str],
+    entries: &BTreeMap<String, T>,
+    commented: bool,
+) {
+    let path = join(path, key);
+    let example_path = format!("{path}.name");
+    write_sample_header(out, docs, true, &format!("[{example_path}]"));
+    T::default().write_sample(out, &example_path, true);
+    if !commented {
+        entries.write_section(out, &path, &[]);
+    }
+}
+
+fn write_sample_header(out: &mut String, docs: &[&str], commented: bool, header: &str) {
+    start_sample_block(out);
+    write_comment(out, docs);
+    if commented {
+        out.push_str("# ");
+    }
+    out.push_str(header);
+    out.push('\n');
+}
+
+/// Separates what follows from what came before with an empty line, unless it directly follows a header.
+fn start_sample_block(out: &mut String) {
+    let last_line = out.trim_end_matches('\n').rsplit('\n').next().unwrap_or("");
+    let after_header = last_line.trim_start_matches("# ").starts_with('[');
+    if !out.is_empty() && !after_header {
+        out.push('\n');
+    }
+}
+
+/// Writes doc comments as `#` comments, one per line.
+pub fn write_comment(out: &mut String, docs: &[&str]) {
+    for line in docs {
+        if line.is_empty() {
+            out.push_str("#\n");
+        } else {
+            out.push_str("# ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    [[servers]]
+    host = "localhost";
+    port = 80;
+
+    [[servers]]
+    host = "example.com";
+
+    [[jobs]] = [];
+    cmd = "build";
+    retries = 0;
+}
+
+#[test]
+fn defaults() {
+    let config = Config::default();
+    let servers = config
+        .servers()
+        .iter()
+        .map(|server| (server.host(), server.port()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![("localhost", 80), ("example.com", 80)], servers);
+    assert!(config.jobs().is_empty());
+}
+
+#[test]
+fn parse_blocks() {
+    let config: Config = r#"
+        [[jobs]]
+        cmd = "test"
+
+        [[jobs]]
+        retries = 2
+        "#
+    .parse()
+    .unwrap();
+    let jobs = config
+        .jobs()
+        .iter()
+        .map(|job| (job.cmd(), job.retries()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![("test", 0), ("build", 2)], jobs);
+    assert_eq!(2, config.servers().len());
+}
+
+#[test]
+fn unknown_key_in_element() {
+    assert_eq!(
+        Some(Error::UnknownKey {
+            path: "jobs[1].args".to_owned()
+        }),
+        "[[jobs]]\n[[jobs]]\nargs = 1".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn display_blocks() {
+    assert_eq!(
+        r#"[[servers]]
+host = "localhost"
+port = 80
+
+[[servers]]
+host = "example.com"
+port = 80
+"#,
+        Config::default().to_string()
+    );
+}
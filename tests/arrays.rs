@@ -0,0 +1,49 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    tags = ["a", "b"];
+    ports = [80, 443];
+    ratios = [0.5];
+    flags = [true, false];
+    hosts = [] as [String];
+}
+
+#[test]
+fn defaults() {
+    let config = Config::default();
+    assert_eq!(&["a".to_owned(), "b".to_owned()], config.tags());
+    assert_eq!(&[80, 443], config.ports());
+    assert_eq!(&[0.5], config.ratios());
+    assert_eq!(&[true, false], config.flags());
+    assert!(config.hosts().is_empty());
+}
+
+#[test]
+fn parse_and_set_arrays() {
+    let mut config: Config = "hosts = [\"a.com\", \"b.com\"]".parse().unwrap();
+    assert_eq!(&["a.com".to_owned(), "b.com".to_owned()], config.hosts());
+    config.try_set("ports", "[8080]").unwrap();
+    assert_eq!(&[8080], config.ports());
+}
+
+#[test]
+fn invalid_element() {
+    assert_eq!(
+        Some(Error::InvalidType {
+            path: "ports[1]".to_owned(),
+            expected: "an integer",
+            found: "string",
+        }),
+        "ports = [1, \"2\"]".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn display_arrays() {
+    assert_eq!(
+        "tags = [\"a\", \"b\"]\nports = [80, 443]\nratios = [0.5]\nflags = [true, false]\nhosts = []\n",
+        Config::default().to_string()
+    );
+}
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+use toml_schema::toml;
+
+toml! {
+    #[name = Config, derive(Debug, Clone, PartialEq), attr(allow(dead_code))]
+
+    port = 8080;
+    format = "JSON" | "YAML";
+    output = { kind = "stdout", color = true } | { kind = "file", path = "out.txt" };
+
+    [options]
+    quiet = false;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    [server]
+    host = "localhost";
+}
+
+#[test]
+fn derives_are_passed_through() {
+    let mut config = Config::default();
+    let original = config.clone();
+    assert_eq!(original, config);
+    config.try_set("options.quiet", "true").unwrap();
+    assert_ne!(original, config);
+    assert!(format!("{config:?}").contains("quiet: true"));
+    assert_eq!(ConfigOutput::Stdout { color: true }, *config.output());
+}
+
+#[test]
+fn tables_override_derives() {
+    let config = Config::default();
+    let servers = HashSet::from([config.server().clone()]);
+    assert!(servers.contains(config.server()));
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use toml_schema::{Error, TomlSchema};
+
+/// Settings for the tool.
+#[derive(TomlSchema, Debug, Clone, PartialEq)]
+#[toml(validate = check_settings)]
+struct Settings {
+    /// Suppress all output
+    quiet: bool,
+    #[toml(range(1..=65535))]
+    port: u16,
+    proxy: Option<String>,
+    tags: Vec<String>,
+    workers: Workers,
+    tls: Option<Tls>,
+    jobs: Vec<Job>,
+    servers: BTreeMap<String, Server>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            quiet: false,
+            port: 8080,
+            proxy: None,
+            tags: vec!["a".to_owned()],
+            workers: Workers::default(),
+            tls: None,
+            jobs: Vec::new(),
+            servers: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(TomlSchema, Debug, Clone, PartialEq)]
+struct Workers {
+    min: u32,
+    max: u32,
+}
+
+impl Default for Workers {
+    fn default() -> Self {
+        Workers { min: 1, max: 4 }
+    }
+}
+
+#[derive(TomlSchema, Debug, Clone, PartialEq, Default)]
+struct Tls {
+    cert: String,
+}
+
+#[derive(TomlSchema, Debug, Clone, PartialEq, Default)]
+struct Job {
+    cmd: String,
+}
+
+#[derive(TomlSchema, Debug, Clone, PartialEq, Default)]
+struct Server {
+    #[toml(range(1..=65535))]
+    port: u16,
+}
+
+fn check_settings(settings: &Settings) -> Result<(), String> {
+    if settings.workers.min > settings.workers.max {
+        return Err("`workers.min` is greater than `workers.max`".to_owned());
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_with_defaults() {
+    let settings: Settings = r#"
+        port = 9000
+
+        [tls]
+        cert = "cert.pem"
+
+        [[jobs]]
+        cmd = "build"
+
+        [servers.prod]
+        port = 443
+        "#
+    .parse()
+    .unwrap();
+    assert!(!settings.quiet());
+    assert_eq!(9000, settings.port());
+    assert_eq!(&["a".to_owned()], settings.tags());
+    assert_eq!(4, settings.workers().max());
+    assert_eq!(Some("cert.pem"), settings.tls().map(Tls::cert));
+    assert_eq!("build", settings.jobs()[0].cmd());
+    assert_eq!(443, settings.servers()["prod"].port());
+}
+
+#[test]
+fn display_round_trip() {
+    let mut settings = Settings::default();
+    settings.try_set("proxy", "http://proxy").unwrap();
+    settings.try_set("tls.cert", "cert.pem").unwrap();
+    settings.try_set("servers.prod.port", "443").unwrap();
+    settings.jobs.push(Job {
+        cmd: "build".to_owned(),
+    });
+    assert_eq!(settings, settings.to_string().parse().unwrap());
+}
+
+#[test]
+fn try_set_undoes_rejected_values() {
+    let mut settings = Settings::default();
+    let original = settings.clone();
+    assert_eq!(
+        Err(Error::Validation {
+            path: String::new(),
+            message: "`workers.min` is greater than `workers.max`".to_owned(),
+        }),
+        settings.try_set("workers.min", "10")
+    );
+    assert_eq!(
+        Err(Error::OutOfRange {
+            path: "port".to_owned(),
+            value: "70000".to_owned(),
+            range: "1..=65535",
+        }),
+        settings.try_set("port", "70000")
+    );
+    assert_eq!(
+        Err(Error::OutOfRange {
+            path: "servers.prod.port".to_owned(),
+            value: "0".to_owned(),
+            range: "1..=65535",
+        }),
+        settings.try_set("servers.prod.port", "0")
+    );
+    assert_eq!(original, settings);
+}
+
+#[test]
+fn integers_outside_of_the_range() {
+    assert_eq!(
+        Err(Error::OutOfRange {
+            path: "port".to_owned(),
+            value: "-1".to_owned(),
+            range: "1..=65535",
+        }),
+        "port = -1".parse::<Settings>()
+    );
+    assert_eq!(
+        Err(Error::OutOfRange {
+            path: "servers.prod.port".to_owned(),
+            value: "70000".to_owned(),
+            range: "1..=65535",
+        }),
+        "[servers.prod]\nport = 70000".parse::<Settings>()
+    );
+}
+
+#[test]
+fn unknown_key() {
+    assert_eq!(
+        Err(Error::UnknownKey {
+            path: "workers.count".to_owned()
+        }),
+        "[workers]\ncount = 1".parse::<Settings>()
+    );
+}
+
+#[test]
+fn sample_toml() {
+    assert_eq!(
+        r#"# Settings for the tool.
+
+# Suppress all output
+# Type: bool
+quiet = false
+
+# Type: u16
+port = 8080
+
+# Type: String, optional
+# proxy = ""
+
+# Type: Vec<String>
+tags = ["a"]
+
+[workers]
+# Type: u32
+min = 1
+
+# Type: u32
+max = 4
+
+# [tls]
+# Type: String
+# cert = ""
+
+# [[jobs]]
+# Type: String
+# cmd = ""
+
+# [servers.name]
+# Type: u16
+# port = 0
+"#,
+        Settings::sample_toml()
+    );
+    assert!(Settings::sample_toml().parse::<Settings>().is_ok());
+}
@@ -0,0 +1,44 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    name = "example";
+    jobs = 4.5;
+
+    [options]
+    quiet = false;
+    output = { name = "build", format = "JSON" };
+
+    [paths]
+    cache = "/tmp";
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        r#"name = "example"
+jobs = 4.5
+
+[options]
+quiet = false
+output = { name = "build", format = "JSON" }
+
+[paths]
+cache = "/tmp"
+"#,
+        Config::default().to_string()
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    let mut config = Config::default();
+    config.try_set("name", "say \"hi\"").unwrap();
+    config.try_set("options.output.format", "YAML").unwrap();
+    let rendered = config.to_string();
+    let parsed: Config = rendered.parse().unwrap();
+    assert_eq!("say \"hi\"", parsed.name());
+    assert_eq!("YAML", parsed.options().output().format());
+    assert_eq!(rendered, parsed.to_string());
+}
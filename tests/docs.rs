@@ -0,0 +1,52 @@
+use toml_schema::toml;
+
+toml! {
+    /// Settings for the app.
+    ///
+    /// Written to `app.toml`.
+    #[name = Config]
+
+    /// Suppress all output
+    quiet = false;
+    port = 8080;
+
+    /// Worker pool
+    [workers]
+    /// Fewest workers
+    min = 1;
+
+    /// Jobs to run
+    [[jobs]]
+    cmd = "build";
+}
+
+#[test]
+fn display_writes_doc_comments() {
+    assert_eq!(
+        r#"# Settings for the app.
+#
+# Written to `app.toml`.
+
+# Suppress all output
+quiet = false
+port = 8080
+
+# Worker pool
+[workers]
+# Fewest workers
+min = 1
+
+# Jobs to run
+[[jobs]]
+cmd = "build"
+"#,
+        Config::default().to_string()
+    );
+}
+
+#[test]
+fn display_round_trip_with_comments() {
+    let config = Config::default();
+    let parsed: Config = config.to_string().parse().unwrap();
+    assert_eq!(config.to_string(), parsed.to_string());
+}
@@ -0,0 +1,38 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    output.format = "JSON";
+    output.color = true;
+
+    [[jobs]]
+    cmd = "build";
+    env.name = "dev";
+
+    [[jobs]]
+    env.name = "prod";
+}
+
+#[test]
+fn dotted_keys_build_tables() {
+    let config = Config::default();
+    assert_eq!("JSON", config.output().format());
+    assert!(config.output().color());
+    let jobs = config
+        .jobs()
+        .iter()
+        .map(|job| (job.cmd(), job.env().name()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![("build", "dev"), ("build", "prod")], jobs);
+}
+
+#[test]
+fn parse_dotted_keys() {
+    let config: Config = "output.format = \"YAML\"".parse().unwrap();
+    assert_eq!("YAML", config.output().format());
+    let config: Config = "output = { color = false }".parse().unwrap();
+    assert!(!config.output().color());
+    let config: Config = "[output]\nformat = \"TOML\"".parse().unwrap();
+    assert_eq!("TOML", config.output().format());
+}
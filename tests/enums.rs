@@ -0,0 +1,51 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    [output]
+    format = "JSON" | "YAML" | "TOML";
+}
+
+#[test]
+fn defaults_to_the_first_value() {
+    assert_eq!(
+        ConfigOutputFormat::Json,
+        Config::default().output().format()
+    );
+}
+
+#[test]
+fn parse_and_set_values() {
+    let mut config: Config = "[output]\nformat = \"YAML\"".parse().unwrap();
+    assert_eq!(ConfigOutputFormat::Yaml, config.output().format());
+    config.try_set("output.format", "TOML").unwrap();
+    assert_eq!(ConfigOutputFormat::Toml, config.output().format());
+    config.try_set("output.format", "\"YAML\"").unwrap();
+    assert_eq!(ConfigOutputFormat::Yaml, config.output().format());
+}
+
+#[test]
+fn from_str_and_display() {
+    assert_eq!(Ok(ConfigOutputFormat::Toml), "TOML".parse());
+    assert_eq!("YAML", ConfigOutputFormat::Yaml.to_string());
+    assert_eq!(
+        "[output]\nformat = \"JSON\"\n",
+        Config::default().to_string()
+    );
+}
+
+#[test]
+fn invalid_variant() {
+    let expected = Error::InvalidVariant {
+        path: "output.format".to_owned(),
+        value: "XML".to_owned(),
+        expected: &["JSON", "YAML", "TOML"],
+    };
+    assert_eq!(
+        Some(expected.clone()),
+        "[output]\nformat = \"XML\"".parse::<Config>().err()
+    );
+    let mut config = Config::default();
+    assert_eq!(Err(expected), config.try_set("output.format", "XML"));
+}
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    port: u16 = 8080;
+    ratio: f32 = 0.5;
+    cache: PathBuf = "/tmp/cache";
+    tags: Vec<String> = ["a"];
+}
+
+#[test]
+fn explicit_types() {
+    let config = Config::default();
+    let port: u16 = config.port();
+    let ratio: f32 = config.ratio();
+    assert_eq!((8080, 0.5), (port, ratio));
+    assert_eq!(Path::new("/tmp/cache"), config.cache());
+    assert_eq!(&["a".to_owned()], config.tags());
+}
+
+#[test]
+fn parse_explicit_types() {
+    let mut config: Config = "port = 443\ncache = \"/var/cache\"".parse().unwrap();
+    assert_eq!(443, config.port());
+    assert_eq!(Path::new("/var/cache"), config.cache());
+    config.try_set("cache", "/srv/cache").unwrap();
+    assert_eq!(Path::new("/srv/cache"), config.cache());
+}
+
+#[test]
+fn integer_out_of_type() {
+    assert_eq!(
+        Some(Error::InvalidValue {
+            path: "port".to_owned(),
+            value: "70000".to_owned(),
+        }),
+        "port = 70000".parse::<Config>().err()
+    );
+}
@@ -0,0 +1,33 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    name = "example";
+    jobs = 4.5;
+
+    [options]
+    quiet = false;
+    output = { name = "build", format = "JSON" };
+}
+
+#[test]
+fn getters_return_defaults() {
+    let config = Config::default();
+    assert_eq!("example", config.name());
+    assert_eq!(4.5, config.jobs());
+    assert!(!config.options().quiet());
+    assert_eq!("build", config.options().output().name());
+    assert_eq!("JSON", config.options().output().format());
+}
+
+#[test]
+fn getters_return_parsed_and_set_values() {
+    let mut config: Config = "name = \"custom\"\n[options]\nquiet = true"
+        .parse()
+        .unwrap();
+    config.try_set("options.output.format", "YAML").unwrap();
+    assert_eq!("custom", config.name());
+    assert!(config.options().quiet());
+    assert_eq!("YAML", config.options().output().format());
+}
@@ -0,0 +1,49 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    [options.output]
+    format = "JSON";
+
+    [options]
+    quiet = false;
+
+    [a.b.c]
+    depth = 3;
+}
+
+#[test]
+fn nested_tables() {
+    let config = Config::default();
+    assert!(!config.options().quiet());
+    assert_eq!("JSON", config.options().output().format());
+    assert_eq!(3, config.a().b().c().depth());
+}
+
+#[test]
+fn parse_and_set_nested_tables() {
+    let mut config: Config = "[options.output]\nformat = \"YAML\"\n[a.b.c]\ndepth = 4"
+        .parse()
+        .unwrap();
+    assert_eq!("YAML", config.options().output().format());
+    assert_eq!(4, config.a().b().c().depth());
+    config.try_set("a.b.c.depth", "5").unwrap();
+    assert_eq!(5, config.a().b().c().depth());
+}
+
+#[test]
+fn display_nested_tables() {
+    assert_eq!(
+        r#"[options]
+quiet = false
+
+[options.output]
+format = "JSON"
+
+[a.b.c]
+depth = 3
+"#,
+        Config::default().to_string()
+    );
+}
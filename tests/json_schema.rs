@@ -0,0 +1,73 @@
+use toml_schema::toml;
+
+toml! {
+    /// Settings for the app.
+    #[name = Config]
+
+    /// Suppress all output
+    quiet = false;
+    name: String;
+    #[range(1..=65535)]
+    port: u16 = 8080;
+    #[pattern = "^[a-z]+$"]
+    #[max_len = 16]
+    host = "localhost";
+    format = "JSON" | "YAML";
+    tags = ["a"];
+
+    [workers]
+    max = 4;
+
+    [[jobs]] = [];
+    cmd: String;
+
+    [servers.*]
+    port = 80;
+}
+
+fn schema() -> serde_json::Value {
+    Config::json_schema().parse().unwrap()
+}
+
+#[test]
+fn document_schema() {
+    let schema = schema();
+    assert_eq!("object", schema["type"]);
+    assert_eq!("Settings for the app.", schema["description"]);
+    assert_eq!(serde_json::json!(["name"]), schema["required"]);
+    assert_eq!(false, schema["additionalProperties"]);
+}
+
+#[test]
+fn property_schemas() {
+    let properties = &schema()["properties"];
+    assert_eq!("boolean", properties["quiet"]["type"]);
+    assert_eq!("Suppress all output", properties["quiet"]["description"]);
+    assert_eq!(false, properties["quiet"]["default"]);
+    assert_eq!("integer", properties["port"]["type"]);
+    assert_eq!(1, properties["port"]["minimum"]);
+    assert_eq!(65535, properties["port"]["maximum"]);
+    assert_eq!(8080, properties["port"]["default"]);
+    assert_eq!("^[a-z]+$", properties["host"]["pattern"]);
+    assert_eq!(16, properties["host"]["maxLength"]);
+    assert_eq!(
+        serde_json::json!(["JSON", "YAML"]),
+        properties["format"]["enum"]
+    );
+    assert_eq!("string", properties["tags"]["items"]["type"]);
+}
+
+#[test]
+fn table_schemas() {
+    let properties = &schema()["properties"];
+    assert_eq!(4, properties["workers"]["properties"]["max"]["default"]);
+    assert_eq!("array", properties["jobs"]["type"]);
+    assert_eq!(
+        serde_json::json!(["cmd"]),
+        properties["jobs"]["items"]["required"]
+    );
+    assert_eq!(
+        80,
+        properties["servers"]["additionalProperties"]["properties"]["port"]["default"]
+    );
+}
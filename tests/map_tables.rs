@@ -0,0 +1,54 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    [servers.*]
+    host = "localhost";
+    port = 80;
+}
+
+#[test]
+fn empty_by_default() {
+    assert!(Config::default().servers().is_empty());
+}
+
+#[test]
+fn parse_entries() {
+    let config: Config = "[servers.prod]\nport = 443\n[servers.staging]\nhost = \"staging\""
+        .parse()
+        .unwrap();
+    let servers = config
+        .servers()
+        .iter()
+        .map(|(name, server)| (name.as_str(), server.host(), server.port()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![("prod", "localhost", 443), ("staging", "staging", 80)],
+        servers
+    );
+    assert_eq!(
+        Some(Error::UnknownKey {
+            path: "servers.prod.tls".to_owned()
+        }),
+        "[servers.prod]\ntls = true".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn try_set_creates_entries() {
+    let mut config = Config::default();
+    config.try_set("servers.prod.port", "443").unwrap();
+    assert_eq!(443, config.servers()["prod"].port());
+    assert_eq!("localhost", config.servers()["prod"].host());
+}
+
+#[test]
+fn display_entries() {
+    let mut config = Config::default();
+    config.try_set("servers.prod.port", "443").unwrap();
+    assert_eq!(
+        "[servers.prod]\nhost = \"localhost\"\nport = 443\n",
+        config.to_string()
+    );
+}
@@ -0,0 +1,24 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config, modules]
+
+    format = "JSON" | "YAML";
+
+    [options.output]
+    color = true;
+
+    [[jobs]]
+    cmd = "build";
+}
+
+#[test]
+fn types_are_named_by_modules() {
+    let config = Config::default();
+    let options: &config::Options = config.options();
+    let output: &config::options::Output = options.output();
+    let jobs: &[config::Jobs] = config.jobs();
+    assert!(output.color());
+    assert_eq!("build", jobs[0].cmd());
+    assert_eq!(config::Format::Json, config.format());
+}
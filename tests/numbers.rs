@@ -0,0 +1,68 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    port = 8080;
+    ratio = 0.5;
+    retries = 3u8;
+    scale = 0.1f32;
+}
+
+#[test]
+fn integers_and_floats() {
+    let config = Config::default();
+    let port: i64 = config.port();
+    let ratio: f64 = config.ratio();
+    let retries: u8 = config.retries();
+    let scale: f32 = config.scale();
+    assert_eq!((8080, 0.5, 3, 0.1), (port, ratio, retries, scale));
+}
+
+#[test]
+fn parse_numbers() {
+    let config: Config = "port = 9000\nratio = 1\nretries = 5".parse().unwrap();
+    assert_eq!(9000, config.port());
+    assert_eq!(1.0, config.ratio());
+    assert_eq!(5, config.retries());
+}
+
+#[test]
+fn fractional_integer() {
+    assert_eq!(
+        Some(Error::InvalidType {
+            path: "port".to_owned(),
+            expected: "an integer",
+            found: "float",
+        }),
+        "port = 1.5".parse::<Config>().err()
+    );
+    let mut config = Config::default();
+    assert_eq!(
+        Err(Error::InvalidType {
+            path: "port".to_owned(),
+            expected: "an integer",
+            found: "float",
+        }),
+        config.try_set("port", "1.5")
+    );
+}
+
+#[test]
+fn integer_out_of_type() {
+    assert_eq!(
+        Some(Error::InvalidValue {
+            path: "retries".to_owned(),
+            value: "300".to_owned(),
+        }),
+        "retries = 300".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn display_numbers() {
+    assert_eq!(
+        "port = 8080\nratio = 0.5\nretries = 3\nscale = 0.1\n",
+        Config::default().to_string()
+    );
+}
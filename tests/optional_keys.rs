@@ -0,0 +1,32 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    proxy?: String;
+    timeout: Option<u32>;
+    port = 8080;
+}
+
+#[test]
+fn optional_keys_default_to_none() {
+    let config = Config::default();
+    assert_eq!(None, config.proxy());
+    assert_eq!(None, config.timeout());
+}
+
+#[test]
+fn parse_and_set_optional_keys() {
+    let mut config: Config = "timeout = 30".parse().unwrap();
+    assert_eq!(Some(30), config.timeout());
+    config.try_set("proxy", "http://proxy").unwrap();
+    assert_eq!(Some("http://proxy"), config.proxy());
+}
+
+#[test]
+fn display_leaves_out_unset_keys() {
+    let mut config = Config::default();
+    assert_eq!("port = 8080\n", config.to_string());
+    config.try_set("timeout", "30").unwrap();
+    assert_eq!("timeout = 30\nport = 8080\n", config.to_string());
+}
@@ -0,0 +1,67 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    name = "example";
+
+    [options]
+    quiet = false;
+    output = { name = "build", format = "JSON" };
+}
+
+#[test]
+fn parse_document() {
+    let config: Config = r#"
+        name = "custom"
+
+        [options]
+        quiet = true
+        output = { name = "release", format = "YAML" }
+        "#
+    .parse()
+    .unwrap();
+    assert_eq!("custom", config.name);
+    assert!(config.options.quiet);
+    assert_eq!("release", config.options.output.name);
+    assert_eq!("YAML", config.options.output.format);
+}
+
+#[test]
+fn missing_keys_take_their_defaults() {
+    let config: Config = "[options]\noutput = { format = \"YAML\" }".parse().unwrap();
+    assert_eq!("example", config.name);
+    assert!(!config.options.quiet);
+    assert_eq!("build", config.options.output.name);
+    assert_eq!("YAML", config.options.output.format);
+}
+
+#[test]
+fn syntax_error() {
+    assert!(matches!(
+        "name = ".parse::<Config>().err(),
+        Some(Error::Syntax(_))
+    ));
+}
+
+#[test]
+fn unknown_key() {
+    assert_eq!(
+        Some(Error::UnknownKey {
+            path: "options.output.level".to_owned()
+        }),
+        "[options]\noutput = { level = 1 }".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn invalid_type() {
+    assert_eq!(
+        Some(Error::InvalidType {
+            path: "options.quiet".to_owned(),
+            expected: "a boolean",
+            found: "integer",
+        }),
+        "[options]\nquiet = 1".parse::<Config>().err()
+    );
+}
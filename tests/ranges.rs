@@ -0,0 +1,74 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    #[range(1..=65535)]
+    port: u16 = 8080;
+    #[range(0.0..1.0)]
+    ratio = 0.5;
+    #[range(1..)]
+    workers = 4;
+    #[range(..=10)]
+    limit: u64 = 5;
+}
+
+fn out_of_range(path: &str, value: &str, range: &'static str) -> Error {
+    Error::OutOfRange {
+        path: path.to_owned(),
+        value: value.to_owned(),
+        range,
+    }
+}
+
+#[test]
+fn parse_checks_ranges() {
+    assert!("port = 1\nratio = 0.0\nworkers = 1\nlimit = 10"
+        .parse::<Config>()
+        .is_ok());
+    assert_eq!(
+        Some(out_of_range("port", "0", "1..=65535")),
+        "port = 0".parse::<Config>().err()
+    );
+    assert_eq!(
+        Some(out_of_range("ratio", "1", "0.0..1.0")),
+        "ratio = 1.0".parse::<Config>().err()
+    );
+    assert_eq!(
+        Some(out_of_range("workers", "0", "1..")),
+        "workers = 0".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn integers_outside_of_the_type() {
+    assert_eq!(
+        Some(out_of_range("port", "70000", "1..=65535")),
+        "port = 70000".parse::<Config>().err()
+    );
+    assert_eq!(
+        Some(out_of_range("port", "-1", "1..=65535")),
+        "port = -1".parse::<Config>().err()
+    );
+    let mut config = Config::default();
+    assert_eq!(
+        Err(out_of_range("limit", "18446744073709551615", "..=10")),
+        config.try_set("limit", "18446744073709551615")
+    );
+}
+
+#[test]
+fn try_set_checks_ranges() {
+    let mut config = Config::default();
+    config.try_set("port", "443").unwrap();
+    assert_eq!(
+        Err(out_of_range("port", "70000", "1..=65535")),
+        config.try_set("port", "70000")
+    );
+    assert_eq!(
+        Err(out_of_range("limit", "11", "..=10")),
+        config.try_set("limit", "11")
+    );
+    assert_eq!(443, config.port());
+    assert_eq!(5, config.limit());
+}
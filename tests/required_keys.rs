@@ -0,0 +1,64 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    name: String;
+    port = 8080;
+
+    [server]
+    host: String;
+    tls = false;
+
+    [[jobs]] = [];
+    cmd: String;
+}
+
+#[test]
+fn constructor_takes_required_values() {
+    let config = Config::new(
+        "example".to_owned(),
+        ConfigServer::new("localhost".to_owned()),
+    );
+    assert_eq!("example", config.name());
+    assert_eq!(8080, config.port());
+    assert_eq!("localhost", config.server().host());
+    assert!(!config.server().tls());
+    assert!(config.jobs().is_empty());
+}
+
+#[test]
+fn parse_required_keys() {
+    let config: Config =
+        "name = \"example\"\n[server]\nhost = \"localhost\"\n[[jobs]]\ncmd = \"build\""
+            .parse()
+            .unwrap();
+    assert_eq!("example", config.name());
+    assert_eq!("build", config.jobs()[0].cmd());
+}
+
+#[test]
+fn missing_key() {
+    assert_eq!(
+        Some(Error::MissingKey {
+            path: "server.host".to_owned()
+        }),
+        "name = \"example\"\n[server]\ntls = true"
+            .parse::<Config>()
+            .err()
+    );
+    assert_eq!(
+        Some(Error::MissingKey {
+            path: "server.host".to_owned()
+        }),
+        "name = \"example\"".parse::<Config>().err()
+    );
+    assert_eq!(
+        Some(Error::MissingKey {
+            path: "jobs[0].cmd".to_owned()
+        }),
+        "name = \"example\"\n[server]\nhost = \"localhost\"\n[[jobs]]"
+            .parse::<Config>()
+            .err()
+    );
+}
@@ -0,0 +1,94 @@
+use toml_schema::toml;
+
+toml! {
+    /// Settings for the app.
+    #[name = Config]
+
+    /// Suppress all output
+    quiet = false;
+    name: String;
+    proxy?: String;
+    port: u16 = 8080;
+    format = "JSON" | "YAML";
+    output = { kind = "stdout", color = true } | { kind = "file", path = "out.txt" };
+    tags = ["a"];
+
+    /// Worker pool
+    [workers]
+    min = 1;
+
+    [servers.*]
+    port = 80;
+
+    [[jobs]]
+    cmd = "a";
+    env.name = "dev";
+
+    [[jobs]]
+    cmd = "b";
+
+    [[hooks]] = [];
+    run: String;
+}
+
+#[test]
+fn sample_toml() {
+    assert_eq!(
+        r#"# Settings for the app.
+
+# Suppress all output
+# Type: bool
+quiet = false
+
+# Type: String, required
+name = ""
+
+# Type: String, optional
+# proxy = ""
+
+# Type: u16
+port = 8080
+
+# Type: one of "JSON", "YAML"
+format = "JSON"
+
+# Type: table, with `kind` one of "stdout", "file"
+output = { kind = "stdout", color = true }
+
+# Type: Vec<String>
+tags = ["a"]
+
+# Worker pool
+[workers]
+# Type: i64
+min = 1
+
+# [servers.name]
+# Type: i64
+# port = 80
+
+[[jobs]]
+# Type: String
+cmd = "a"
+
+[jobs.env]
+# Type: String
+name = "dev"
+
+[[jobs]]
+cmd = "b"
+
+# [[hooks]]
+# Type: String, required
+# run = ""
+"#,
+        Config::sample_toml()
+    );
+}
+
+#[test]
+fn sample_toml_parses() {
+    let config: Config = Config::sample_toml().parse().unwrap();
+    assert_eq!(2, config.jobs().len());
+    assert!(config.hooks().is_empty());
+}
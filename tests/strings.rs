@@ -0,0 +1,74 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    #[pattern = "^[a-z]+$"]
+    host = "localhost";
+    #[min_len = 1]
+    #[max_len = 8]
+    label = "app";
+    #[min_len = 2]
+    code = "ab";
+}
+
+#[test]
+fn pattern_mismatch() {
+    let expected = Error::PatternMismatch {
+        path: "host".to_owned(),
+        value: "Local Host".to_owned(),
+        pattern: "^[a-z]+$",
+    };
+    assert_eq!(
+        Some(expected.clone()),
+        "host = \"Local Host\"".parse::<Config>().err()
+    );
+    let mut config = Config::default();
+    assert_eq!(Err(expected), config.try_set("host", "Local Host"));
+    assert_eq!("localhost", config.host());
+}
+
+#[test]
+fn invalid_length() {
+    assert_eq!(
+        Some(Error::InvalidLength {
+            path: "label".to_owned(),
+            value: String::new(),
+            min: Some(1),
+            max: Some(8),
+        }),
+        "label = \"\"".parse::<Config>().err()
+    );
+    let mut config = Config::default();
+    assert_eq!(
+        Err(Error::InvalidLength {
+            path: "code".to_owned(),
+            value: "a".to_owned(),
+            min: Some(2),
+            max: None,
+        }),
+        config.try_set("code", "a")
+    );
+}
+
+#[test]
+fn invalid_length_message() {
+    let error = |min, max| Error::InvalidLength {
+        path: "label".to_owned(),
+        value: String::new(),
+        min,
+        max,
+    };
+    assert_eq!(
+        "invalid value for `label`: `` must be between 1 and 8 characters long",
+        error(Some(1), Some(8)).to_string()
+    );
+    assert_eq!(
+        "invalid value for `label`: `` must be at least 1 characters long",
+        error(Some(1), None).to_string()
+    );
+    assert_eq!(
+        "invalid value for `label`: `` has an invalid length",
+        error(None, None).to_string()
+    );
+}
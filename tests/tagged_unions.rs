@@ -0,0 +1,58 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    output = { kind = "stdout", color = true } | { kind = "file", path = "out.txt" };
+}
+
+#[test]
+fn defaults_to_the_first_alternative() {
+    assert!(matches!(
+        Config::default().output(),
+        ConfigOutput::Stdout { color: true }
+    ));
+}
+
+#[test]
+fn parse_alternatives() {
+    let config: Config = "output = { kind = \"file\" }".parse().unwrap();
+    assert!(matches!(config.output(), ConfigOutput::File { path } if path == "out.txt"));
+    assert_eq!(
+        Some(Error::UnknownKey {
+            path: "output.color".to_owned()
+        }),
+        "output = { kind = \"file\", color = true }"
+            .parse::<Config>()
+            .err()
+    );
+}
+
+#[test]
+fn try_set_switches_alternatives() {
+    let mut config = Config::default();
+    config.try_set("output.kind", "file").unwrap();
+    assert!(matches!(config.output(), ConfigOutput::File { path } if path == "out.txt"));
+    config.try_set("output.path", "log.txt").unwrap();
+    assert!(matches!(config.output(), ConfigOutput::File { path } if path == "log.txt"));
+    assert_eq!(
+        Err(Error::UnknownKey {
+            path: "output.color".to_owned()
+        }),
+        config.try_set("output.color", "false")
+    );
+}
+
+#[test]
+fn display_alternatives() {
+    let mut config = Config::default();
+    assert_eq!(
+        "output = { kind = \"stdout\", color = true }\n",
+        config.to_string()
+    );
+    config.try_set("output.kind", "file").unwrap();
+    assert_eq!(
+        "output = { kind = \"file\", path = \"out.txt\" }\n",
+        config.to_string()
+    );
+}
@@ -0,0 +1,57 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config]
+
+    name = "example";
+
+    [options]
+    quiet = false;
+    output = { name = "build", format = "JSON" };
+}
+
+#[test]
+fn try_set_dotted_keys() {
+    let mut config = Config::default();
+    config.try_set("name", "custom").unwrap();
+    config.try_set("options.quiet", "true").unwrap();
+    config.try_set("options.output.format", "YAML").unwrap();
+    assert_eq!("custom", config.name);
+    assert!(config.options.quiet);
+    assert_eq!("YAML", config.options.output.format);
+}
+
+#[test]
+fn try_set_raw_and_quoted_strings() {
+    let mut config = Config::default();
+    config.try_set("name", "raw text").unwrap();
+    assert_eq!("raw text", config.name);
+    config.try_set("name", "\"quoted\"").unwrap();
+    assert_eq!("quoted", config.name);
+    config.try_set("name", "'literal'").unwrap();
+    assert_eq!("literal", config.name);
+}
+
+#[test]
+fn try_set_unknown_key() {
+    let mut config = Config::default();
+    assert_eq!(
+        Err(Error::UnknownKey {
+            path: "options.verbose".to_owned()
+        }),
+        config.try_set("options.verbose", "true")
+    );
+}
+
+#[test]
+fn try_set_invalid_value() {
+    let mut config = Config::default();
+    assert_eq!(
+        Err(Error::InvalidValue {
+            path: "options.quiet".to_owned(),
+            value: "maybe".to_owned(),
+        }),
+        config.try_set("options.quiet", "maybe")
+    );
+    assert!(!config.options.quiet);
+}
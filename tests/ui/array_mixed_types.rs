@@ -0,0 +1,9 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    values = [1u8, 2.5];
+}
+
+fn main() {}
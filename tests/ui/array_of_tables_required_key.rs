@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    [[jobs]]
+    cmd: String;
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    [[jobs]]
+    cmd = "a";
+
+    [[jobs]] = [];
+    cmd = "b";
+}
+
+fn main() {}
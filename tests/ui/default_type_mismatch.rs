@@ -0,0 +1,9 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    port: u16 = "8080";
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config, derive(Debug, Default)]
+
+    quiet = false;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use toml_schema::TomlSchema;
+
+#[derive(TomlSchema, Default)]
+struct Settings {
+    #[toml(range(1..=300))]
+    level: u8,
+}
+
+fn main() {}
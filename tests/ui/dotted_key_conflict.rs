@@ -0,0 +1,12 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    output.format = "JSON";
+
+    [output]
+    name = "build";
+}
+
+fn main() {}
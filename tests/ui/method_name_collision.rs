@@ -0,0 +1,9 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    try_set = 1;
+}
+
+fn main() {}
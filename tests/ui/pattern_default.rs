@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    #[pattern = "^[a-z]+$"]
+    host = "Local Host";
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    #[range(1..=65535)]
+    port: u16 = 0;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    #[range(0.5..=10.5)]
+    x = 1;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    #[range(-5..=10)]
+    x: u16 = 1;
+}
+
+fn main() {}
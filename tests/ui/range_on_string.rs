@@ -0,0 +1,10 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    #[range(1..10)]
+    name = "example";
+}
+
+fn main() {}
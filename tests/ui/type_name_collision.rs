@@ -0,0 +1,13 @@
+use toml_schema::toml;
+
+toml! {
+    #[name = Config]
+
+    [a_b]
+    x = 1;
+
+    [a]
+    b = { y = 2 };
+}
+
+fn main() {}
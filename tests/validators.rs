@@ -0,0 +1,62 @@
+use toml_schema::{toml, Error};
+
+toml! {
+    #[name = Config, validate = check_config]
+
+    name = "example";
+
+    #[validate = check_workers]
+    [workers]
+    min = 1;
+    max = 4;
+}
+
+fn check_config(config: &Config) -> Result<(), String> {
+    if config.name().is_empty() {
+        return Err("`name` is empty".to_owned());
+    }
+    Ok(())
+}
+
+fn check_workers(workers: &ConfigWorkers) -> Result<(), String> {
+    if workers.min() > workers.max() {
+        return Err("`min` is greater than `max`".to_owned());
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_runs_validators() {
+    assert_eq!(
+        Some(Error::Validation {
+            path: "workers".to_owned(),
+            message: "`min` is greater than `max`".to_owned(),
+        }),
+        "[workers]\nmin = 5".parse::<Config>().err()
+    );
+    assert_eq!(
+        Some(Error::Validation {
+            path: String::new(),
+            message: "`name` is empty".to_owned(),
+        }),
+        "name = \"\"".parse::<Config>().err()
+    );
+}
+
+#[test]
+fn try_set_undoes_rejected_values() {
+    let mut config = Config::default();
+    assert_eq!(
+        Err(Error::Validation {
+            path: "workers".to_owned(),
+            message: "`min` is greater than `max`".to_owned(),
+        }),
+        config.try_set("workers.min", "10")
+    );
+    assert_eq!(1, config.workers().min());
+    assert!(config.try_set("name", "").is_err());
+    assert_eq!("example", config.name());
+    config.try_set("workers.max", "16").unwrap();
+    config.try_set("workers.min", "10").unwrap();
+    assert_eq!(10, config.workers().min());
+}
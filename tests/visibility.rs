@@ -0,0 +1,26 @@
+mod schema {
+    use toml_schema::toml;
+
+    toml! {
+        #[name = Config, visibility(pub(crate)), field_visibility(pub)]
+
+        name = "example";
+
+        #[field_visibility(pub(crate))]
+        [options]
+        quiet = false;
+    }
+}
+
+use schema::{Config, ConfigOptions};
+
+#[test]
+fn fields_are_visible() {
+    let config = Config {
+        name: "custom".to_owned(),
+        options: ConfigOptions { quiet: true },
+    };
+    assert_eq!("custom", config.name());
+    assert!(config.options.quiet);
+    assert!(config.options().quiet());
+}